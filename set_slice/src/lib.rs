@@ -1,4 +1,6 @@
 #![no_std]
+// `diagnostic::do_not_recommend` is newer than the minimum supported Rust version, older compilers ignore it with a warning
+#![allow(unknown_or_malformed_diagnostic_attributes)]
 
 // Copyright 2018 lazy-static.rs Developers
 //
@@ -17,19 +19,137 @@ without setting each value individually
 ```ignore
 set_slice! {
     SLICE = VALUE_1, VALUE_2, VALUE_3, ...;          // list
+    SLICE = MIN..=MAX: VALUE_1, VALUE_2, ...;        // checked list
+    SLICE[INDEX] = VALUE;                            // single element
+    #[name = NAME] SLICE = ...;                      // named instruction
+    SLICE[RANGE], SLICE[RANGE], ... = OPTION VALUE;  // same value to many ranges
     SLICE = move VALUE;                              // move
+    SLICE = take_forget VALUE;                       // move and forget
+    SLICE = move_default SOURCE;                     // move and reset source
     SLICE = clone REFERENCE;                         // clone ref
     SLICE = copy REFERENCE;                          // copy ref
+    SLICE = arr_copy REFERENCE;                      // copy array ref
+    VEC = extend REFERENCE;                          // extend with ref
+    VEC = extend copy REFERENCE;                     // grow to fit, then copy ref
+    VEC = extend clone REFERENCE;                    // grow to fit, then clone ref
+    SLICE = into REFERENCE;                          // convert ref
+    SLICE = from_le REFERENCE;                       // copy little-endian ref
+    SLICE = from_be REFERENCE;                       // copy big-endian ref
+    SLICE = to_owned REFERENCE;                      // to owned ref
+    SLICE = copy SOURCE[RANGE];                      // copy source range
+    SLICE = copy &SOURCE[min = LENGTH];              // copy source prefix
+    SLICE = copy &SOURCE[max = LENGTH];              // copy short source
+    SLICE = volatile copy REFERENCE;                 // volatile copy ref
+    SLICE = truncate copy REFERENCE;                 // copy shorter of both
+    SLICE = saturating_add copy REFERENCE;           // saturating add ref
+    SLICE =OVERFLOW+= copy REFERENCE;                // add ref on overflow
+    SLICE =OVERFLOW*= copy REFERENCE;                // multiply ref on overflow
+    SLICE = some_all copy REFERENCE;                 // copy ref as Some
+    with_stats SLICE = copy REFERENCE                // copy ref with stats
+    diff_log SLICE = copy REFERENCE                  // copy ref with changes
+    with_count SLICE[MASK] = fill VALUE              // fill masked, count
+    with_count SLICE[MASK] = copy REFERENCE          // copy masked, count
+    SLICE[!MASK] = copy REFERENCE;                   // copy unmasked
+    SLICE[RANGE; step STEP] = VALUE_1, VALUE_2, ...; // list into every STEPth element
+    SLICE[RANGE; step STEP] = copy REFERENCE;        // copy into every STEPth element
+    SLICE[RANGE; step STEP] = clone REFERENCE;       // clone into every STEPth element
+    SLICE.FIELD = copy_field &SOURCE.FIELD;          // copy one field
+    SLICE <=> OTHER;                                 // swap two slices
+    SLICE = iter ITERATOR;                           // move from iterator
+    SLICE = iter_ref ITERATOR;                       // clone from iterator
+    SLICE = from_deque DEQUE;                        // copy deque
+    SLICE = from_deque clone DEQUE;                  // clone deque
+    SLICE = none_all;                                // set all to None
+    SLICE = default;                                 // reset to default
+    SLICE = ramp(START, END);                        // evenly spaced floats
+    SLICE = fade_in;                                 // ramp from 0 to 1
+    SLICE = fade_out;                                // ramp from 1 to 0
+    SLICE = window(WINDOW);                          // window coefficients
+    SLICE = normalize NORM;                          // scale to unit norm
+    SLICE = normalize checked NORM;                  // scale or panic
     unsafe SLICE: (SIZE) = ref REFERENCE;            // unsafe copy ref
+    unsafe SLICE = ref REFERENCE;                    // unsafe copy ref, any size
+    SLICE @ (D1, D2) [X, Y, ..] = ...;               // line of a 3d buffer
+    SLICE at(OFFSET, LENGTH) = ...;                  // span at an offset
+    SLICE[RANGE] = ... => rest                       // write, return the rest
+    SLICE @ (WIDTH) rect(X, Y, W, H) = fill VALUE;   // fill 2d rect
+    SLICE @ (WIDTH) rect(X, Y, W, H) = copy REFERENCE; // blit 2d rect
+    GRID col(COLUMN) = copy REFERENCE;               // copy column of nested arrays
+    GRID row(ROW) = ...;                             // row of nested arrays
+    GRID[ROWS][COLUMNS] = copy REFERENCE;            // copy 2d block of nested arrays
+    GRID[ROWS][COLUMNS] = clone REFERENCE;           // clone 2d block of nested arrays
+    fence(ORDERING);                                 // compiler fence
+    atomic fence(ORDERING);                          // memory fence
+    cover: SLICE => { [START..END] = ...; ... }      // tile the slice
+    SLICE => (head, rest) = (VALUE, ...);            // split first
+    SLICE => (rest, last) = (..., VALUE);            // split last
+    SLICE = reverse;                                 // reverse in place
+    SLICE = reverse copy REFERENCE;                  // copy ref reversed
+    SLICE = reverse clone REFERENCE;                 // clone ref reversed
+    SLICE = map FUNCTION;                            // map in place
+    SLICE = push_back VALUE;                         // slide window left
+    SLICE = push_front VALUE;                        // slide window right
+    SLICE = try_fill_with FACTORY                    // fallible fill
+    SLICE = fill VALUE;                              // fill with value
+    SLICE = clone_fill VALUE;                        // same as fill
+    SLICE = with FUNCTION;                           // fill from index
+    SLICE = spread VALUE;                            // clone value into all
+    SLICE = fill_copy VALUE;                         // copy value into all
+    SLICE = copy_fill VALUE;                         // same as fill_copy
+    SLICE = merge REFERENCE, REFERENCE;              // merge sorted refs
+    SLICE = rotated_left(AMOUNT) REFERENCE;          // rotated copy ref
+    SLICE = lut(TABLE) INDICES;                      // look up indices
+    SLICE = lut(TABLE, clamp) INDICES;               // look up clamped indices
+    SLICE = repeat_n(COUNT) copy REFERENCE;          // copy ref COUNT times
+    SLICE = repeat REFERENCE;                        // tile ref
+    SLICE = repeat clone REFERENCE;                  // tile cloned ref
+    SLICE[RANGE] = copy_within SOURCE_RANGE;         // move within the slice
+    SLICE = init copy REFERENCE                      // initialize, produce &mut [T]
+    SLICE = init clone REFERENCE                     // initialize clones, produce &mut [T]
+    SLICE = saturating copy REFERENCE;               // copy as much of ref as fits
+    SLICE = saturating clone REFERENCE               // clone as much of ref as fits, produce the count
+    SLICE = deplane(STRIDE, OFFSET) REFERENCE;       // deinterleave ref
+    SLICE = replane(STRIDE, OFFSET) copy REFERENCE;  // interleave ref
+    SLICE = permute(PERMUTATION) copy REFERENCE;     // gather copy ref
+    SLICE = permute(PERMUTATION) clone REFERENCE;    // gather clone ref
+    SLICE = scatter_permute(PERMUTATION) copy REFERENCE; // scatter copy ref
+    SLICE = clamp_copy REFERENCE, LOW, HIGH;         // clamped copy ref
+    SLICE = fma REFERENCE, REFERENCE, SCALAR;        // fused multiply-add
+    SLICE = fma REFERENCE, REFERENCE;                // fused accumulate
+    SLICE = dedup_into REFERENCE;                    // dedup copy ref
+    SLICE[..LENGTH] = add_unique REFERENCE;          // insert into small set
+    SLICE = copy_until(SENTINEL) REFERENCE;          // copy up to sentinel
+    SLICE = copy_until(SENTINEL, truncate) REFERENCE; // copy up to sentinel or full
+    SLICE = checksum ALGORITHM REFERENCE;            // checksum of ref
+    SLICE = parity REFERENCE;                        // xor parity of ref
+    SLICE = transpose_bits8 REFERENCE;               // transpose 8x8 bits
+    SLICE = pack ENDIAN INT REFERENCE;               // pack ints as bytes
+    SLICE = unpack ENDIAN INT REFERENCE;             // unpack bytes as ints
+    SLICE = bytes STRING;                            // copy utf-8 bytes of a str
+    SLICE = from_bytes VALUE;                        // native-endian bytes of an int
+    SLICE = from_le_bytes VALUE;                     // little-endian bytes of an int
+    SLICE = from_be_bytes VALUE;                     // big-endian bytes of an int
+    VEC = runs &SOURCE PREDICATE map FUNCTION;       // summarize runs
+    SLICE = variant_table(Default) { VARIANT => VALUE, ... }; // enum table
     ...
 }
 ```
 ## Variable Definitions
-`SLICE: &mut [T]` = name of slice \
+`SLICE: impl AsMut<[T]>` = name of slice, like an array, a `&mut [T]`, a `Vec<T>` or a `Box<[T]>` \
 `VALUE: impl Deref<Target = [T]> | AsRef<[T]>` = value to be stored in slice \
+`MIN`/`MAX` = literals that bound the values of a checked list, `MIN..MAX` is also allowed \
+`D1`/`D2: usize` = the last two dimensions of a flat 3d buffer \
+`X`/`Y: usize` = the indices of a line along the last dimension of a flat 3d buffer \
+`DEQUE: &VecDeque<T>` = a reference to a ring buffer with an `as_slices` method \
 `SIZE: usize` = a constexpr that specifies the size of the slice \
 `REFERENCE: &[T]` = a reference to a slice \
-`copy`/`clone` = an identifier that speficies how to handle REFERENCE
+`SOURCE[RANGE]` = a range of a slice, with bounds checked by set_slice \
+`SCALAR: T` = a single value used for every element \
+`AMOUNT: usize` = the number of elements to rotate by \
+`ALGORITHM` = the checksum to compute, either `crc32` or `fletcher16` \
+`WINDOW` = the window function to sample, one of `hann`, `hamming` or `blackman` \
+`NORM` = what to scale a float slice by, either `sum` or `max` \
+`copy`/`clone`/`into`/`to_owned` = an identifier that speficies how to handle REFERENCE
 
 ## Examples
 ```rust
@@ -54,310 +174,7894 @@ assert_eq!(slice, [-1, -2, 3]);
 if multiple instructions are present, then each instruction is executed in order \
 and can overwrite previous instructions
 
+some instructions produce a value, to get it the instruction must be the only one \
+in the macro, and it must not end with a semicolon, e.g. `let n = set_slice!(a = dedup_into &b);`
+
+the modes that write the same way to the whole region, `reverse`, `map` and `fill`, produce that region \
+as a `&mut [T]`, so it can be handed on right away, e.g. `let region = set_slice!(a[2..6] = reverse);` \
+the region mutably borrows the slice for as long as the region is used
+
+## targets
+any `SLICE` that implements `AsMut<[T]>` can be assigned to, including a generic one, \
+and `SLICE[RANGE]` indexes the slice it produces, so the same instructions work for every container
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+fn reset<S: AsMut<[u8]>>(mut buf: S) -> S {
+    set_slice! {
+        buf = fill 0;
+        buf[..2] = 0xca, 0xfe;
+    }
+    buf
+}
+
+assert_eq!(reset([1; 3]), [0xca, 0xfe, 0]);
+assert_eq!(*reset(vec![1; 3].into_boxed_slice()), [0xca, 0xfe, 0]);
+# }
+```
+
+`SLICE` can also be a chain of fields and method calls, like `self.buffer` or `grid.row_mut(2)` \
+each instruction evaluates its target expression exactly once, and only borrows it for that instruction, \
+so a method call in the target runs once for every instruction that uses it
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+struct Packet {
+    buffer: [u8; 6],
+}
+
+impl Packet {
+    fn write_header(&mut self, header: &[u8; 4]) {
+        set_slice! {
+            self.buffer[..4] = copy header;
+            self.buffer[4..] = fill 0;
+        }
+    }
+}
+
+let mut packet = Packet { buffer: [0xff; 6] };
+packet.write_header(b"SETS");
+
+assert_eq!(packet.buffer, *b"SETS\0\0");
+# }
+```
+
+fixed capacity vectors, like `heapless::Vec<T, N>`, implement `AsMut<[T]>` and `Deref<Target = [T]>`, \
+so they can be used as a target, or as a `REFERENCE` with `&vec` \
+the slice they produce only covers their length, not their capacity, so a target must be resized before it is assigned to, \
+e.g. with `resize_default`, and indexing past the length panics like indexing a slice, \
+use a span, `buf at(0, n)`, instead of `buf[..n]` to panic with the line of the instruction \
+with the `heapless` feature, `VEC = extend REFERENCE;` in `try_set_slice!` grows a `heapless::Vec` up to its capacity, \
+and produces an error with the kind `SetSliceErrorKind::Alloc` instead of writing anything if the values do not fit
+
+## 3d lines
+`SLICE @ (D1, D2) [X, Y, ..]` treats `SLICE` as a flat `D0 x D1 x D2` buffer, and targets the contiguous line \
+along the last dimension at `X, Y`, which is `SLICE[(X * D1 + Y) * D2..][..D2]` \
+any instruction can be used with it, e.g. `volume @ (4, 8) [x, y, ..] = copy &line;`, and the line is `D2` long \
+if `Y` is not less than `D1`, or the line is out of bounds of `SLICE`, this will panic
+
+## spans
+`SLICE at(OFFSET, LENGTH)` targets the `LENGTH` elements of `SLICE` starting at `OFFSET`, which is `SLICE[OFFSET..OFFSET + LENGTH]` \
+any instruction can be used with it, e.g. `buf at(off, len) = copy &src;` or `buf at(off, len) = fill 0;` \
+the end is computed with `checked_add`, so if `OFFSET + LENGTH` overflows, or the span is out of bounds of `SLICE`, \
+this panics with the line of the instruction, instead of wrapping around or naming the range
+
+## steps
+`SLICE[RANGE; step STEP]` targets every `STEP`th element of `SLICE[RANGE]`, starting at its first element, \
+so `audio[1..; step 3] = copy &right;` writes to `audio[1]`, `audio[4]`, `audio[7]`, ... \
+it can be used with a list, `copy REFERENCE` or `clone REFERENCE`, and the values are written one element at a time \
+if the length of the range is not a multiple of `STEP`, the last slot is the first element of the last, shorter stride, \
+so there are `ceil(len / STEP)` slots, and the number of values must be exactly that \
+a `STEP` of 0 panics with the line of the instruction
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut audio = [0; 7];
+
+set_slice! {
+    audio[..; step 3] = 1, 2, 3;
+    audio[1..5; step 2] = copy &[8, 9];
+}
+
+assert_eq!(audio, [1, 8, 0, 9, 0, 0, 3]);
+# }
+```
+
+## remainders
+`SLICE[RANGE] = ... => rest` runs a list or an `OPTION VALUE` instruction, like `copy REFERENCE` or `fill VALUE`, \
+and produces the unwritten remainder after the end of `RANGE`, so `buf[..n] = copy &src => rest` produces `&mut buf[n..]` \
+it must be the only instruction in the macro, and the remainder mutably borrows `SLICE` for as long as it is used, \
+so a buffer can be built up in stages by writing to each remainder in turn
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut buf = [0u8; 8];
+
+let rest = set_slice!(buf[..2] = copy &[0xca, 0xfe] => rest);
+let rest = set_slice!(rest[..3] = 1, 2, 3 => rest);
+set_slice! { rest = fill 9; }
+
+assert_eq!(buf, [0xca, 0xfe, 1, 2, 3, 9, 9, 9]);
+# }
+```
+
+## multiple targets
+`SLICE[RANGE_1], SLICE[RANGE_2], ... = OPTION VALUE;` runs `OPTION VALUE` once for each of the targets, in order, \
+e.g. `buf[0..4], buf[8..12] = copy &values;` mirrors `values` into both ranges \
+`VALUE` is evaluated once, so it must be a reference, and each target is checked against it on its own, \
+so every range must be as long as `VALUE`, but the targets can be in different slices
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut frame = [0u8; 8];
+let mut backup = [0u8; 3];
+
+set_slice! {
+    frame[1..3], frame[5..7], backup[1..] = copy &[0xaa, 0xbb];
+}
+
+assert_eq!(frame, [0, 0xaa, 0xbb, 0, 0, 0xaa, 0xbb, 0]);
+assert_eq!(backup, [0, 0xaa, 0xbb]);
+# }
+```
+
+## 2d rects
+`SLICE @ (WIDTH) rect(X, Y, W, H)` treats `SLICE` as a flat image with rows `WIDTH` elements long, \
+and targets the `W x H` rectangle whose top left corner is at column `X` of row `Y` \
+`fill VALUE` clones `VALUE` into every element of the rectangle, \
+and `copy REFERENCE` copies the `REFERENCE` `&[T]` values into it row by row, so `REFERENCE` must be `W * H` long \
+each row is written as one contiguous span, with `copy_from_slice` for `copy` \
+if the rectangle does not fit in the width or in `SLICE`, this will panic before anything is written \
+`T` must implement `Clone` for `fill`, and `Copy` for `copy`
+
+## nested rows and columns
+`GRID col(COLUMN) = copy REFERENCE;` treats `GRID` as a slice of rows, such as `[[T; C]; R]` or `Vec<Vec<T>>`, \
+and copies `REFERENCE[r]` into `GRID[r][COLUMN]` for every row `r`, so `REFERENCE` must be as long as there are rows \
+since a column is not contiguous, each element is written on its own, and `T` must implement `Copy` \
+`GRID row(ROW)` is the contiguous row `GRID[ROW]`, and any instruction can be used with it, e.g. `grid row(1) = copy &row;` \
+if `COLUMN` or `ROW` is out of bounds, this will panic
+
+`GRID[ROWS][COLUMNS] = copy REFERENCE;` targets the block of columns `COLUMNS` in each of the rows `ROWS`, \
+and copies (or clones, with `clone`) successive chunks of the flat `REFERENCE` `&[T]` into it, one chunk per row \
+unlike indexing outside of the macro, `COLUMNS` indexes each row, not the slice of rows, \
+and `ROWS` must be a range, so for a single row use `grid[r..=r][COLUMNS]` \
+the column span must be the same length in every row, and `REFERENCE` must be as long as the whole block, \
+both are checked before anything is written, and a mismatch panics with the line of the instruction
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut image = [[0u8; 4]; 3];
+
+set_slice! {
+    image[1..][1..3] = copy &[1, 2, 3, 4];
+}
+
+assert_eq!(image, [[0, 0, 0, 0], [0, 1, 2, 0], [0, 3, 4, 0]]);
+# }
+```
+
+## single elements
+`SLICE[INDEX] = VALUE;` and `SLICE[INDEX] = move VALUE;` set just the one element at `INDEX`, \
+with the usual bounds check of indexing, so they are the same as `SLICE[INDEX] = VALUE` outside of the macro \
+`INDEX` must be an integer literal, so that it can not be mistaken for a range, \
+for a computed index use a range of one element, like `slice[i..=i] = VALUE;` \
+`SLICE[INDEX] = ref VALUE;` is rejected, just like for a range
+
 ## list
 the list: `VALUE_1`, `VALUE_2`, `VALUE_3`, ... is counted and converted into an array \
-after conversion it is has the same semantics as move applied to the new array
+after conversion it is has the same semantics as move applied to the new array \
+a list of a single reference, like `slice = &vec;`, still stores that reference if the slice holds references, \
+otherwise it is almost always a missing `copy` or `clone`, so the type error suggests them
+
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut slice = [0; 3];
+let vec = [1, 2, 3];
+
+set_slice! {
+    slice = &vec; // error: did you mean `copy` or `clone` before this reference?
+}
+# }
+```
+
+**breaking change:** a list of a single local named like a mode that takes no value, \
+`reverse`, `default`, `none_all`, `fade_in` or `fade_out`, is now that mode, \
+e.g. `slice = default;` resets the slice instead of storing a local called `default` \
+wrap the local in parentheses, like `slice = (default);`, or use `move [default]` to store it
+
+## named instructions
+`#[name = "packet_header"]` before a list, `move`, `copy`, `clone`, `into`, `to_owned`, `from_le` or `from_be` instruction \
+gives it a name, which is included in its length mismatch panic after the line, \
+e.g. `line 2 (packet_header): value length (3) is invalid, expected: 4` \
+this is meant for generated code, where the line alone does not say much about which assignment failed \
+`NAME` must be a string literal, or a macro that expands to one, like `concat!` or `stringify!`
+
+## checked list
+the same as a list, but each value is checked to be within `MIN..=MAX` (or `MIN..MAX`) \
+if every value is a literal, the check is done at compile time and an out of range value is a compile error \
+otherwise the check is done at run-time with `debug_assert!`, so it is skipped in release builds
+
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut table = [0u16; 3];
+
+set_slice! {
+    table = 0..=255: 10, 20, 300; // error: value 300 is out of range 0..=255
+}
+# }
+```
 
 ## move
 the `VALUE` is moved into set_slice and dropped \
-the contents of `VALUE` are stored into the slice
+the contents of `VALUE` are stored into the slice \
+`VALUE` is captured as it was written, so moving the same binding in two instructions is a use after move error \
+that points at the second `VALUE` in the macro input, not at the macro
+
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut a = vec![String::new()];
+let mut b = vec![String::new()];
+let names = vec![String::from("x")];
+
+set_slice! {
+    a = move names;
+    b = move names; // error: use of moved value: `names`
+}
+# }
+```
+
+## move_default
+the contents of `SOURCE` are moved into the slice, and `SOURCE` is reset with `T::default()` \
+unlike `move`, `SOURCE` is not a temporary, it is a named `&mut [T]` (or anything that can be indexed mutably) \
+that is still usable afterwards, and the old contents of the slice are dropped \
+the lengths must be equal, and `T` must implement `Default`
+
+## take_forget
+the contents of `VALUE` are moved into the slice, and the old contents of the slice are dropped \
+but `VALUE` itself is forgotten with `mem::forget` semantics, so its destructor never runs \
+each value ends up dropped exactly once, even if dropping an old value panics \
+**note:** a `VALUE` that owns its storage, like a `Vec`, leaks that storage, prefer arrays
+
+## copy
+the `REFERENCE` `&[T]` values are copied into the slice \
+`T` must implement `Copy` \
+safe code can not make `REFERENCE` overlap the slice, but if unsafe code does, debug builds panic with the line of the instruction \
+see `copy_within` to move values inside one slice
+
+## clone
+the `REFERENCE` `&[T]` values are cloned into the slice \
+`T` must implement `Clone`, and if it is also known to implement `Copy` the values are copied with `copy_from_slice` instead \
+like `copy`, debug builds panic if unsafe code made `REFERENCE` overlap the slice
+
+## copy
+the `REFERENCE` `&[T]` values are copied into the slice \
+`T` must implement `Copy`
+
+## arr_copy
+the `REFERENCE` `&[T; N]` array is copied into the `[T; N]` array with a single array assignment \
+both must be fixed-size arrays of the same length `N`, so there is no length check at runtime, \
+and a mismatch is a compile error \
+`T` must implement `Copy`
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut dst = [0u8; 4];
+let src = [1u8; 3];
+
+set_slice! { dst = arr_copy &src; }
+# }
+```
+
+## compile-time length checks
+when `copy`, `clone`, `into`, `to_owned`, `from_le` or `from_be` write a fixed-size array, \
+or a literal range of one, like `v[..2]`, `v[1..=3]` or `v[2..]`, and `REFERENCE` is a reference to an array, \
+both lengths are known at compile time, and a mismatch is a compile error instead of a panic \
+otherwise, such as for a `&[T]` or a `Vec<T>`, the lengths are still checked at runtime
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut dst = [0u8; 4];
+let src = [1u8; 3];
+
+set_slice! { dst[..2] = copy &src; }
+# }
+```
+
+a list written to a literal range is counted, so its length is always known at compile time \
+if both ends of the range are literals, like `v[0..3]` or `v[..=2]`, a wrong number of values is a compile error \
+for any slice, and with a range open at the end, like `v[2..]`, it is when `v` is a fixed-size array
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut v = vec![0; 4];
+
+set_slice! { v[0..3] = 1, 2; } // error: the number of values does not match the length of the range
+# }
+```
+
+## extend
+the `REFERENCE` `&[T]` values are cloned onto the end of `VEC` with its `extend_from_slice` method, \
+so `VEC` may grow, and there is no length check \
+this works with any growable buffer that has that method, such as `Vec` or `smallvec::SmallVec`, \
+and a `SmallVec` that outgrows its inline buffer just spills to the heap \
+`SmallVec::extend_from_slice` needs `T: Copy`, and with the `smallvec` feature, `try_set_slice!` can extend a `SmallVec` too \
+the fixed length modes, such as `copy`, also work on these buffers through `DerefMut`, and check the length as usual \
+`T` must implement `Clone`
+
+`VEC = extend copy REFERENCE;` and `VEC = extend clone REFERENCE;` grow `VEC` to fit instead of checking the length, \
+if `VEC` is shorter than `REFERENCE`, the missing values are cloned onto its end with `extend_from_slice`, \
+then the first values of `VEC` are copied or cloned from `REFERENCE` as usual, \
+so `VEC` starts with `REFERENCE`, and if it was longer, the values past the end of `REFERENCE` are left untouched \
+`VEC` must be a growable buffer, so this can't be used with a range, or a plain slice
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut samples = vec![0; 2];
+
+set_slice! {
+    samples = extend copy &[1, 2, 3, 4];
+    samples = extend copy &[5];
+}
+
+assert_eq!(samples, [5, 2, 3, 4]);
+# }
+```
+
+## into
+the `REFERENCE` `&[T]` values are cloned and converted into the slice with `Into` \
+so that `slice[i] = value[i].clone().into()` \
+`T` must implement `Clone` and `Into<U>`, where `U` is the element type of the slice
+
+## from_le / from_be
+the `REFERENCE` `&[T]` integers are copied into the slice, converting each one from little-endian (`from_le`) \
+or big-endian (`from_be`) byte order to the native one, like `T::from_le` \
+the path is chosen with `cfg(target_endian)`, so when the byte order already matches \
+this is a plain `copy_from_slice`, and otherwise the bytes of each element are swapped during the copy \
+`T` must be one of the primitive integer types
+
+## to_owned
+the `REFERENCE` `&[&B]` references are converted into owned values with `ToOwned` \
+so that `slice[i] = value[i].to_owned()`, e.g. from `&[&str]` into `&mut [String]` \
+`B` must implement `ToOwned<Owned = U>`, where `U` is the element type of the slice \
+this requires the `alloc` feature
+
+## source ranges
+`copy SOURCE[RANGE]` and `clone SOURCE[RANGE]` behave like `copy &SOURCE[RANGE]` and `clone &SOURCE[RANGE]` \
+but the range is checked against the length of `SOURCE` first, and the panic names the range \
+that was out of bounds, which helps when the bounds are computed at run-time
+
+## min / max
+by default, the length of the value must be equal to the length of the slice, \
+but `copy` and `clone` can check the length of `SOURCE` against a bound `LENGTH` instead \
+`&SOURCE[min = LENGTH]` checks that `SOURCE` has at least `LENGTH` values, and uses the first `LENGTH` of them, \
+the slice must then be exactly `LENGTH` long \
+`&SOURCE[max = LENGTH]` checks that `SOURCE` has at most `LENGTH` values, and writes all of them to the start of the slice, \
+the slice must then be at least as long as `SOURCE`, and the rest of the slice is left untouched \
+the panic messages read "expected at least" or "expected at most" respectively \
+no other mode supports these bounds \
+the `=` keeps them apart from source ranges, so `&src[min..max]` still indexes `src` with locals named `min` and `max`
+
+## truncate copy / truncate clone / saturating copy / saturating clone
+the `REFERENCE` `&[T]` values are copied (or cloned) into the slice, but only as many as fit in both, \
+so that `min(slice.len(), REFERENCE.len())` elements at the start of the slice are written, and the rest are left untouched \
+unlike `copy`, a length mismatch never panics, but with `truncate` and the `std` feature in debug builds, \
+each time that values are actually cut off a warning naming the line is printed to stderr with `eprintln!` \
+this is meant for moving from exact lengths to flexible ones, the warnings show where the lengths still differ \
+`saturating` is the same without the warning, for when a shorter slice is expected \
+when it is the only instruction, without a semicolon, either one produces the number of values written as a `usize` \
+`T` must implement `Copy` for `copy`, and `Clone` for `clone`
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut line = [b'.'; 8];
+let written = set_slice!(line[2..] = saturating copy b"truncated");
+
+assert_eq!(written, 6);
+assert_eq!(&line, b"..trunca");
+# }
+```
+
+## volatile copy
+the `REFERENCE` `&[T]` values are written into the slice one at a time, in order, using `core::ptr::write_volatile` \
+so the compiler will not elide or reorder any of the writes, this is intended for memory mapped registers \
+**note:** the writes are not atomic, and they do not imply any memory ordering with respect to other threads \
+`T` must implement `Copy`
+
+## saturating_add copy
+the `REFERENCE` `&[T]` values are added into the slice with saturating arithmetic, \
+so that `slice[i] = slice[i].saturating_add(value[i])`, and a sum that overflows clips at `T::MAX` or `T::MIN` \
+instead of wrapping around, as needed when mixing audio samples \
+the lengths must be equal, and `T` must be one of the primitive integer types
+
+## =OVERFLOW+= copy / =OVERFLOW*= copy
+the `REFERENCE` `&[T]` values are added to (`+=`) or multiplied into (`*=`) the slice, \
+with the overflow behaviour spelled out by `OVERFLOW`, so it does not change between debug and release builds \
+`wrapping` wraps around, `saturating` clips at `T::MAX` or `T::MIN`, and `checked` panics on the first overflow, \
+naming the index, e.g. `acc =wrapping+= copy &delta;` or `gain =checked*= copy &scale;` \
+the lengths must be equal, and `T` must be one of the primitive integer types
+
+## some_all / none_all
+`some_all copy` and `some_all clone` store each of the `REFERENCE` `&[T]` values into a `&mut [Option<T>]` as `Some` \
+so that `slice[i] = Some(value[i])`, `T` must implement `Copy` or `Clone` respectively \
+`none_all` sets every element of a `&mut [Option<T>]` to `None`, there is no value, so there is no length to check
+
+## default
+every element of the slice is overwritten with a fresh `T::default()`, e.g. `scratch[..n] = default;` \
+there is no value, so there is no length to check, and since `Default` is in `core`, this works in `no_std` \
+`T` must implement `Default`
+
+## with_stats
+the `REFERENCE` `&[T]` values are copied into the slice, and the minimum, maximum and sum of \
+the values are computed in the same pass, this produces an `Option<SliceStats<T>>` \
+so it can only be used as the only instruction, without a semicolon \
+if the slice is empty, this produces `None` \
+`T` must implement `Ord`, `Copy` and `Add<Output = T>`
+
+## diff_log
+the `REFERENCE` `&[T]` values are copied into the slice, and every element that changed is recorded \
+as an `(index, old, new)` entry in the same pass, this produces a `Vec<(usize, T, T)>` in order of the index \
+so it can only be used as the only instruction, without a semicolon, e.g. `let log = set_slice!(diff_log buf = copy &src);` \
+the index is relative to the start of the targeted range, so the log can be replayed, or undone by writing each `old` back \
+`T` must implement `PartialEq` and `Copy`, and this requires the `alloc` feature
+
+## with_count
+`MASK: &[bool]` selects the elements of the slice to write, and must be as long as the slice \
+`fill VALUE` clones `VALUE` into every element where the mask is `true`, \
+and `copy REFERENCE` copies the `REFERENCE` `&[T]` values in order into those elements, \
+so `REFERENCE` must be exactly as long as the number of `true`s in the mask \
+this produces the number of elements written, which is the number of `true`s in the mask, \
+whether or not the values actually changed, so it can only be used as the only instruction, without a semicolon \
+`T` must implement `Clone` for `fill`, and `Copy` for `copy`
+
+`SLICE[!MASK]` negates the mask, so only the elements where it is `false` are written, \
+and the count of `false`s takes the place of the count of `true`s \
+a negated mask can also be used without `with_count`, as a plain `SLICE[!MASK] = copy REFERENCE;` statement, \
+`SLICE[MASK]` can not, since a plain `SLICE[...]` is always a range
+
+## swap
+`SLICE <=> OTHER;` exchanges the contents of two slices in place, and either may be a range, \
+e.g. `front[..4] <=> back;` or `buf[..4] <=> buf[4..8];`, so neither side is dropped, unlike with `move` \
+the lengths must be equal, and if both are ranges of the same buffer that overlap, like `buf[..4] <=> buf[2..6]`, \
+this panics instead of swapping
+
+## copy_field
+`SLICE.FIELD = copy_field &SOURCE.FIELD;` copies a single field of each element of `SOURCE`, \
+into the same element of the slice, so that `slice[i].FIELD = source[i].FIELD`, and the other fields are left untouched \
+e.g. `rows.1 = copy_field &update.1;` replaces the second column of a table of tuples, the fields may differ, \
+and either side may be a range, e.g. `rows[2..].0 = copy_field &ids[..3].0;` \
+the lengths must be equal, and the type of the field must implement `Copy`
+
+## iter
+`ITERATOR: IntoIterator<Item = T>` is consumed, and each of its items is moved into the slice, in order \
+e.g. `buf[2..6] = iter line.split(',').map(parse);`, without collecting it into a temporary first \
+the iterator must produce exactly `slice.len()` items, if it runs out early, or has any left over, this panics \
+the elements before that point have already been written
+
+## iter_ref
+`ITERATOR: IntoIterator<Item = &T>` yields references, and exactly `slice.len()` of them are cloned into the slice, in order \
+e.g. `buf = iter_ref map.values();` \
+any items after those are not pulled from the iterator, and if it runs out early, this panics \
+the elements before that point have already been written \
+`T` must implement `Clone`
+
+## from_deque
+the contents of `DEQUE` are copied (or cloned with `from_deque clone`) into the slice, from front to back \
+the two halves of the ring buffer are found with `VecDeque::as_slices`, so no allocation is made \
+`T` must implement `Copy` (or `Clone`)
+
+## unsafe copy
+**VERY UNSAFE** \
+the `REFERENCE` `&[T]` values are copied into the slice \
+internally the old values are dropped, and then the new ones are copied bitwise with `core::ptr::copy_nonoverlapping`, \
+without building a `[T; SIZE]` on the stack \
+`(SIZE)` may be left out, as in `unsafe v[3..6] = ref &values;`, then the size is the length of the slice, \
+which is only known at runtime, and `REFERENCE` must have the same length \
+this works with every range form, including the open ones, like `v[2..]`, `v[..n]` or `v[..]`, \
+whose length depends on the slice, and with `(SIZE)` both lengths are checked against `SIZE` instead, \
+so the size of every unsafe copy can be spelled out when it is audited \
+so, use this with caution, as it may cause undefined behaviour \
+**VERY UNSAFE**
+
+## fence
+`fence(ORDERING)` is a statement on its own, it calls `core::sync::atomic::compiler_fence` \
+so that the compiler may not reorder the writes of the statements before it past the operations after it \
+this is only a compiler fence, it does not emit any instructions, so it is enough for signal handlers \
+and for code where the hardware ordering is provided by the flag itself, such as a `Release` store \
+`atomic fence(ORDERING)` calls `core::sync::atomic::fence` instead, which is a full memory barrier \
+`ORDERING` must be one of `Acquire`, `Release`, `AcqRel` or `SeqCst`
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+use std::sync::atomic::{AtomicBool, Ordering};
+
+let ready = AtomicBool::new(false);
+let mut buf = [0; 3];
+let src = [1, 2, 3];
+
+set_slice! {
+    buf = copy &src;
+    fence(Release);
+}
+
+ready.store(true, Ordering::Release);
+# assert!(ready.load(Ordering::Relaxed));
+# assert_eq!(buf, src);
+# }
+```
+
+## cover
+`cover: SLICE => { [0..4] = ...; [4..8] = ...; }` assigns each range of the slice with any of the modes that go after `=`, \
+and checks that the ranges tile the slice exactly once, in order \
+the ranges must be literals, and they are checked at compile time to start at 0, with no gaps or overlaps between them \
+the end of the last range must equal the length of the slice, which is checked at run-time before anything is written
+
+```compile_fail
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut buf = [0; 8];
+
+set_slice! {
+    cover: buf => {
+        [0..4] = 1, 2, 3, 4;
+        [5..8] = 6, 7, 8; // error: there is a gap 4..5 before range 5..8
+    }
+}
+# }
+```
+
+## split first / last
+`SLICE => (head, rest) = (VALUE, ...)` moves `VALUE` into the first element of the slice, \
+and assigns the rest of the slice with any of the modes that go after `=`, such as `copy REFERENCE` \
+`SLICE => (rest, last) = (..., VALUE)` does the same with the last element \
+the slice must not be empty, and the rest is length checked like any other statement
+
+## reverse
+the contents of the slice are reversed in place, using `<[T]>::reverse` \
+there is no value, so there is no length to check
+
+## reverse copy
+the `REFERENCE` `&[T]` values are copied or cloned into the slice in reverse order, \
+so that `slice[i] = value[value.len() - 1 - i]`, and the slice must be as long as `REFERENCE` \
+this writes each element once, in a single pass over both slices, \
+unlike `copy` followed by `reverse`, which writes the slice twice, and `REFERENCE` is left untouched \
+`T` must implement `Copy` for `reverse copy`, or `Clone` for `reverse clone`
+
+## map
+`FUNCTION: FnMut(T) -> T` is applied to each element of the slice in place \
+there is no value, so there is no length to check \
+`T` must implement `Copy` \
+the generated code is a plain loop over the slice with no bounds checks, \
+which leaves simple arithmetic on `f32` or integer slices open to auto-vectorization in release builds \
+e.g. with rustc 1.95 on x86_64, `data = map |x| x * 2.0 + 1.0;` on a `&mut [f32]` compiles to packed `addps` instructions, \
+but that is up to the optimizer, and not guaranteed
+
+## push_back / push_front
+the slice is treated as a fixed size sliding window \
+`push_back` shifts every element left by one, dropping the first one, and writes `VALUE` into the last slot \
+`push_front` shifts every element right by one, dropping the last one, and writes `VALUE` into the first slot \
+an empty slice is left untouched \
+`T` must implement `Copy`
+
+## try_fill_with
+`FACTORY: FnMut() -> Result<T, E>` is called once for each element of the slice, in order, \
+and the value is stored into that element, this produces a `Result<(), E>` \
+so it can only be used as the only instruction, without a semicolon \
+on the first error, no more elements are written and the error is produced \
+the elements before the failed one keep their new values, and the rest keep their old values \
+in `try_set_slice!`, it can be one instruction among others, see [Fallible assignment](#fallible-assignment)
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut handles = [0; 4];
+let mut next = 0;
+
+let result: Result<(), &str> = set_slice!(handles = try_fill_with || {
+    next += 1;
+    if next < 4 { Ok(next) } else { Err("out of handles") }
+});
+
+assert_eq!(result, Err("out of handles"));
+assert_eq!(handles, [1, 2, 3, 0]);
+# }
+```
+
+## fill
+`VALUE` is put into every element of the slice with `<[T]>::fill`, which clones it for each element \
+since the whole slice is filled, there is no length check, and this works with every range form, \
+e.g. `slice[2..8] = fill 0;`, `slice[1..=3] = fill 0;` or just `slice = fill 0;` \
+`clone_fill` is another name for it, to match `copy_fill` \
+`T` must implement `Clone`
+
+`fill`, `fill_copy` and `default` are the fill family, they only differ in how each element is made, \
+so they work with the same targets, take a value or not the same way, and when one of them is the only instruction, \
+without a semicolon, it produces the filled region as a `&mut [T]`
+
+| mode | each element is | `T` must implement |
+|---|---|---|
+| `fill VALUE` or `clone_fill VALUE` | a clone of `VALUE` | `Clone` |
+| `fill_copy VALUE` or `copy_fill VALUE` | a copy of `VALUE` | `Copy` |
+| `default` | `T::default()` | `Default` |
+
+## with
+`FUNCTION: FnMut(usize) -> T` is called with the index of each element, in order, and its result is stored there \
+the index is relative to the start of the targeted range, so `slice[4..8] = with |i| i;` stores `0, 1, 2, 3` \
+since the whole slice is filled, there is no length check, e.g. `ramp = with |i| i as u8 * 2;`
+
+## spread
+`VALUE` is cloned into every element of the slice but the last, and then moved into the last element \
+so a slice of length `n` takes `n - 1` clones, and an empty slice just drops `VALUE` \
+each element is written with `Clone::clone_from`, so the old value is dropped (or reused) as the new one is written \
+if a clone panics, the elements before it hold clones of `VALUE`, the elements after it keep their old values, \
+and `VALUE` is dropped, so every element is still a valid `T` and nothing is dropped twice \
+`T` must implement `Clone`
+
+## fill_copy / copy_fill
+`VALUE` is evaluated once and copied into every element of the slice, without calling `Clone` at all \
+this is the `Copy` counterpart to `fill` and `spread`, and because it is a plain broadcast of the same bits \
+the compiler can turn it into a `memset` for byte-sized values \
+`copy_fill` is another name for it, both take a value, so `copy_fill ref VALUE` is a compile error \
+`T` must implement `Copy`, and `Clone` is never called, so this works in `no_std` code that avoids it
+
+## merge
+the two sorted `REFERENCE`s are merged into the slice, so that the slice is also sorted \
+the length of the slice must be the sum of the lengths of both `REFERENCE`s \
+equal elements are taken from the first `REFERENCE` first \
+`T` must implement `Ord` and `Copy` \
+**note:** the `REFERENCE`s must already be sorted, this is not checked
+
+## clamp_copy
+the `REFERENCE` `&[T]` values are copied into the slice, each clamped between the matching elements \
+of the `LOW` and `HIGH` `&[T]` bounds, so that `slice[i] = value[i].clamp(low[i], high[i])`, in a single pass \
+the lengths of `REFERENCE`, `LOW` and `HIGH` must all equal the length of the slice, \
+and the panic names the one that does not \
+`T` must implement `Ord` and `Copy`, and like `Ord::clamp` this panics if `low[i] > high[i]`
+
+## ramp / fade_in / fade_out
+`ramp(START, END)` fills the slice with evenly spaced values from `START` to `END`, both included, \
+so that `slice[i] = START + (END - START) * i / (slice.len() - 1)`, and a slice of one element is set to `START` \
+`fade_in` is `ramp(0.0, 1.0)` and `fade_out` is `ramp(1.0, 0.0)`, e.g. `gains = fade_out;` for the gain envelope of a fade-out \
+`T` must be `f32` or `f64`
+
+## window
+`window(WINDOW)` fills the slice with the coefficients of the symmetric window function `WINDOW`, \
+sampled at as many points as the slice is long, e.g. `coeffs = window(hann);` before taking a spectrum \
+the windows are `hann`, `hamming` and `blackman`, and a slice of one element is set to `1.0` \
+the coefficients are computed with `cos`, so `T` must be `f32` or `f64`, and `std` must be available
+
+## normalize
+`normalize sum` divides every element by the sum of the slice, so that afterwards it sums to `1.0`, \
+as for a probability distribution, and `normalize max` divides by the largest element, so that the peak is `1.0` \
+the total is computed in a first pass, and the slice is scaled in a second, both in `f64` \
+if the total is zero, or not finite, which includes an empty slice, there is nothing sensible to divide by, \
+so `normalize` leaves the slice unchanged, and `normalize checked` panics instead \
+`T` must be `f32` or `f64`
+
+## fma
+`fma &a, &b, scalar` computes `slice[i] = a[i] * scalar + b[i]` \
+`fma &a, &b` computes `slice[i] += a[i] * b[i]` \
+both are computed with a single rounding using `mul_add`, so `T` must be `f32` or `f64`, and `std` must be available \
+the lengths of all of the `REFERENCE`s must equal the length of the slice
+
+## rotated_left
+the `REFERENCE` `&[T]` values are copied into the slice, rotated left by `AMOUNT` \
+so that `slice[i] = value[(i + AMOUNT) % value.len()]`, and `REFERENCE` is left untouched \
+this is the non-mutating counterpart to `<[T]>::rotate_left` \
+`T` must implement `Copy`
+
+## lut
+every index of `INDICES` `&[I]` is looked up in the `TABLE` `&[T]`, so that `slice[i] = table[indices[i] as usize]`, \
+e.g. `rgb = lut(&palette) &pixels;` expands indexed colors, or `out = lut(&gamma) &input;` applies gamma correction \
+the slice must be as long as `INDICES`, and an index that is out of bounds of `TABLE` panics with the line of the instruction, \
+unless `clamp` is given, then it is clamped to the last entry of `TABLE` instead \
+`T` must implement `Copy`, and `I` must implement `Copy + Into<usize>`, like `u8` and `u16`
+
+## repeat_n
+the `REFERENCE` `&[T]` values are copied into the slice `COUNT` times, one copy after another, \
+so the slice must be exactly `COUNT * REFERENCE.len()` long, e.g. `buf = repeat_n(3) copy &unit;` \
+`COUNT` must be an integer literal, so the number of `copy_from_slice` calls is a constant, \
+and for the small counts this is meant for, the optimizer unrolls them instead of looping \
+`T` must implement `Copy`
+
+## repeat
+the `REFERENCE` `&[T]` pattern is copied over and over until the slice is full, e.g. `buf[..256] = repeat &[0xde, 0xad];` \
+unlike `repeat_n`, the number of copies is not given, but the slice must be an exact multiple of the pattern length \
+`T` must implement `Copy`, or `Clone` for `repeat clone REFERENCE`
+
+## copy_within
+the elements of `SLICE[SOURCE_RANGE]` are copied to the start of `SLICE[RANGE]`, with `<[T]>::copy_within`, \
+so the source and the destination may overlap, e.g. `buf[1..] = copy_within 0..4;` shifts 4 elements right by one \
+both ranges index the same slice, so unlike `copy`, there is no separate `REFERENCE` to borrow \
+if `RANGE` is open at the end, it only needs room for the source, otherwise it must be exactly as long as the source \
+an out of bounds range panics with the line of the instruction \
+`T` must implement `Copy`
+
+## init
+the `REFERENCE` `&[T]` values are copied or cloned into an uninitialized `&mut [MaybeUninit<T>]` slice with `MaybeUninit::write`, \
+and when it is the only instruction, without a semicolon, it produces the now initialized region as a `&mut [T]` \
+the slice must be as long as `REFERENCE`, like `copy`, and using `init` on a slice that is not `[MaybeUninit<T>]` is a compile error \
+the old contents of the slots are never read or dropped, so writing to initialized slots leaks their values \
+`T` must implement `Copy` for `init copy`, or `Clone` for `init clone`
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+use std::mem::MaybeUninit;
+
+let mut buf = [MaybeUninit::<u16>::uninit(); 4];
+let header = set_slice!(buf[..2] = init copy &[0xca, 0xfe]);
+
+assert_eq!(header, [0xca, 0xfe]);
+# }
+```
+
+## deplane
+every `STRIDE`th value of the interleaved `REFERENCE` `&[T]`, starting at `OFFSET`, is copied into the slice \
+so that `slice[i] = value[i * STRIDE + OFFSET]`, e.g. `red = deplane(4, 0) &rgba;` extracts the red plane of RGBA pixels \
+`OFFSET` must be less than `STRIDE`, and the slice must be `REFERENCE.len() / STRIDE` long \
+`T` must implement `Copy`
+
+## replane
+the inverse of `deplane`, the `REFERENCE` `&[T]` plane is written into every `STRIDE`th element of the slice, \
+starting at `OFFSET`, so that `slice[i * STRIDE + OFFSET] = value[i]`, with either `copy` or `clone` \
+the other elements are left untouched, so one `replane` per channel builds up the interleaved buffer \
+`OFFSET` must be less than `STRIDE`, and the slice must be `REFERENCE.len() * STRIDE` long \
+`T` must implement `Copy` for `copy`, and `Clone` for `clone`
+
+## permute
+the `REFERENCE` `&[T]` values are gathered into the slice through `PERMUTATION: &[usize]`, \
+so that `slice[i] = value[perm[i]]`, with either `copy` or `clone` \
+the length of `PERMUTATION` must equal the length of the slice, and every index must be less than `REFERENCE.len()` \
+all of the indices are checked before anything is written \
+`T` must implement `Copy` for `copy`, and `Clone` for `clone`
+
+## scatter_permute
+the inverse of `permute`, the `REFERENCE` `&[T]` values are scattered into the slice through `PERMUTATION: &[usize]`, \
+so that `slice[perm[i]] = value[i]`, with either `copy` or `clone` \
+the lengths of `PERMUTATION` and `REFERENCE` must equal the length of the slice, and every index must be in bounds \
+in debug builds `PERMUTATION` is also checked to be a true permutation, without duplicates, \
+this check takes quadratic time, and in release builds a duplicate index just means that the last write wins \
+`T` must implement `Copy` for `copy`, and `Clone` for `clone`
+
+## dedup_into
+the `REFERENCE` `&[T]` values are copied into the slice, but consecutive runs of equal values \
+are collapsed into their first value, like `Vec::dedup`, and `REFERENCE` is left untouched \
+this produces the number of values written, which is at most `REFERENCE.len()` \
+the slice must have room for every value that is written, anything after that is left untouched, \
+so a slice as long as `REFERENCE` is always enough \
+`T` must implement `PartialEq` and `Copy`
+
+## add_unique
+`SLICE[..LENGTH]` is a small set, its first `LENGTH` elements are the members, and the rest of `SLICE` is free room \
+each of the `REFERENCE` `&[T]` values that is not a member yet is cloned into the next free element, in order \
+this produces the new number of members, e.g. `len = set_slice!(members[..len] = add_unique &candidates);` \
+every candidate is compared with every member, so this is meant for small sets that can not allocate \
+if a new member does not fit in `SLICE`, this panics, and the members before it have already been added \
+`T` must implement `PartialEq` and `Clone`
+
+## copy_until
+the `REFERENCE` `&[T]` values before the first one equal to `SENTINEL` are copied into the start of the slice, \
+or all of them if there is no `SENTINEL`, and the sentinel itself is not copied \
+this produces the number of values copied, anything after them in the slice is left untouched \
+if the slice fills up before the `SENTINEL` is found, this panics, \
+unless `truncate` is given, then only as many values as fit in the slice are copied \
+`T` must implement `PartialEq` and `Copy`
+
+## checksum
+the checksum of the `REFERENCE` `&[u8]` bytes is computed and written into the slice as big-endian bytes \
+the slice must be exactly as long as the checksum, 4 bytes for `crc32` and 2 bytes for `fletcher16` \
+the checksum is computed before the slice is borrowed, so `REFERENCE` may be another part of the same buffer \
+`crc32` is the IEEE 802.3 CRC used by ethernet, zip and png \
+`fletcher16` is the Fletcher checksum with two 8-bit sums taken modulo 255
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut packet = [0u8; 13];
+let n = 9;
+
+set_slice! {
+    packet[..n] = copy b"123456789";
+    packet[n..] = checksum crc32 &packet[..n];
+}
+
+assert_eq!(packet[n..], [0xcb, 0xf4, 0x39, 0x26]);
+# }
+```
+
+## parity
+the XOR parity (longitudinal redundancy check) of the `REFERENCE` `&[T]` values is computed \
+by folding them with `^`, starting from `T::default()`, and written into the slice \
+the slice must be exactly one element long, e.g. `frame[last..] = parity &frame[..last];` \
+`T` may be a byte or a wider word, and must implement `Copy`, `Default` and `BitXor<Output = T>`
+
+## transpose_bits8
+the `REFERENCE` `&[u8; 8]` is an 8x8 bit matrix, where byte `i` is row `i` and the most significant bit is column 0 \
+its transpose is written into the `[u8; 8]` array, so that bit `j` of row `i` becomes bit `i` of row `j` \
+this uses the standard three-step delta swap on a `u64`, and both sizes are checked at compile time
+
+## pack
+the `REFERENCE` `&[INT]` integers are written into the `&mut [u8]` slice as bytes, one after another \
+`ENDIAN` is either `be` for big-endian (network byte order) or `le` for little-endian, \
+and `INT` is any primitive integer type, such as `u16`, `u32`, `u64` or `i32` \
+the slice must be exactly `REFERENCE.len()` times the size of `INT` long
+
+## bytes
+the UTF-8 bytes of the `STRING` `&str` are copied into the `&mut [u8]` slice, like `copy STRING.as_bytes()`, \
+e.g. `buf[..5] = bytes "HELLO";` \
+the slice must be as long as the string in bytes, not in `char`s, so `bytes "é"` needs 2 bytes
+
+## from_bytes
+the bytes of the integer `VALUE` are copied into the `&mut [u8]` slice, e.g. `header[4..8] = from_le_bytes len;` \
+`from_bytes` uses the native byte order, `from_le_bytes` little-endian and `from_be_bytes` big-endian (network byte order) \
+`VALUE` is any primitive integer, and the slice must be exactly as long as its size, like 4 bytes for a `u32`
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut header = [0u8; 6];
+
+set_slice! {
+    header[..2] = from_be_bytes 0xcafe_u16;
+    header[2..] = from_le_bytes 5_u32;
+}
+
+assert_eq!(header, [0xca, 0xfe, 5, 0, 0, 0]);
+# }
+```
+
+## unpack
+the inverse of `pack`, the `REFERENCE` `&[u8]` bytes are read as `INT` integers into the `&mut [INT]` slice, \
+one after another, e.g. `words = unpack le u32 &bytes;` when parsing a binary format \
+`ENDIAN` is either `be` or `le`, and `INT` is any primitive integer type \
+`REFERENCE` must be exactly `slice.len()` times the size of `INT` long
+
+## runs
+`SOURCE` is split into runs of consecutive elements, where `PREDICATE: FnMut(&T, &T) -> bool` \
+is called on each pair of neighbours and returns `true` if they belong to the same run \
+`FUNCTION: FnMut(&[T]) -> U` is called on each run in order, and `VEC` is set to the summaries \
+since the number of runs is only known at runtime, `VEC` must be a `Vec<U>`, and it is cleared first \
+`SOURCE` may be indexed with a range, e.g. `runs &src[2..] |a, b| a == b map |run| run.len()` \
+this requires the `alloc` feature
+
+## variant_table
+builds a table that is indexed by the discriminants of a field-less enum \
+every slot is filled with `T::default()`, then each `VALUE` is moved into the slot at `VARIANT as usize` \
+so any gaps between the listed variants keep the default value \
+instead of `Default`, any expression may be given, which is then cloned into every slot \
+if a variant is out of bounds of the slice, this will panic \
+`T` must implement `Default`, or `Clone` when an expression is given
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+enum Op { Nop, Load, Store, Jump }
+let mut cycles = [0; 4];
+
+set_slice! {
+    cycles = variant_table(Default) { Op::Load => 3, Op::Store => 4 };
+}
+
+assert_eq!(cycles, [0, 3, 4, 0]);
+# }
+```
+
+# Sequential writes
+
+`@cursor SLICE:` turns the rest of the macro into a sequence of writes, each one starting where the last one ended
+
+```ignore
+set_slice! {
+    @cursor SLICE:
+    write REFERENCE;                                 // copy ref at cursor
+    write le INT VALUE;                              // little-endian bytes
+    write be INT VALUE;                              // big-endian bytes
+    ...
+}
+```
+
+the cursor starts at 0, and each `write` copies its values at the cursor and moves it past them \
+`write le INT &value` and `write be INT &value` write the bytes of the integer `value` of type `INT`, \
+so `SLICE` must be a `&mut [u8]` to use them \
+if a write does not fit in the rest of `SLICE`, this panics before it is done \
+if the last write does not end with a semicolon, this produces the final cursor position
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut pkt = [0u8; 16];
+let header = [0xca, 0xfe];
+let body = *b"hello";
+
+let len = set_slice! {
+    @cursor pkt:
+    write &header;
+    write le u32 &(body.len() as u32);
+    write &body
+};
+
+assert_eq!(len, 11);
+assert_eq!(&pkt[..len], b"\xca\xfe\x05\0\0\0hello");
+# }
+```
+
+# Fallible assignment
+
+`try_set_slice!` takes the same list, `move`, `copy` and `clone` instructions as `set_slice!`, \
+but instead of panicking when a value has the wrong length, it stops at that instruction \
+and produces an `Err(SetSliceError)`, otherwise it produces `Ok(())` \
+the instructions before the failed one have already been executed, and the ones after it are not \
+`SetSliceError::line` is the 1-based line of the failed instruction, as reported by the panics of `set_slice!`, \
+`SetSliceError::statement_index` is the same index counted from 0, and `SetSliceError::kind` says why it failed \
+the `Display` of the error is the same message that `set_slice!` would have panicked with, without the `#[name]`, \
+and with the `std` feature, it implements `std::error::Error`
+
+with the `alloc` feature, `VEC = extend REFERENCE;` can be used too, for a `Vec` \
+room for all of `REFERENCE` is reserved with `Vec::try_reserve` before anything is written, \
+so if the allocation fails, `VEC` is left untouched, and the error has the kind `SetSliceErrorKind::Alloc`
+
+`SLICE = try_fill_with FACTORY;` can be used too, and an `Err(E)` from `FACTORY` stops the macro like a length error, \
+with the elements filled before it keeping their new values \
+then the whole macro produces a `Result<(), E>` instead, its length errors are turned into `E` with `From<SetSliceError>`, \
+and every `try_fill_with` in it must have the same error type
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut a = [0; 2];
+let mut b = [0; 3];
+
+let result = try_set_slice! {
+    a = copy &[1, 2];
+    b = copy &[3, 4];
+};
+
+match result {
+    Ok(()) => unreachable!(),
+    Err(e) => {
+        assert_eq!(e.statement_index, 1);
+        assert_eq!((e.expected, e.actual), (3, 2));
+    }
+}
+assert_eq!(a, [1, 2]);
+# }
+```
+
+since the whole macro is a `Result<(), SetSliceError>`, or `Result<(), E>` with `try_fill_with`, it can be the tail of a function that returns one, \
+or be followed by `?` to hand the error on
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# use set_slice::SetSliceError;
+fn check_header(magic: &[u8], version: &[u8]) -> Result<(), SetSliceError> {
+    let mut m = [0u8; 4];
+    let mut v = [0u8; 2];
+
+    try_set_slice! {
+        m = copy magic;
+        v = copy version;
+    }
+}
+
+fn check_all(headers: &[(&[u8], &[u8])]) -> Result<usize, SetSliceError> {
+    for &(magic, version) in headers {
+        check_header(magic, version)?;
+    }
+
+    Ok(headers.len())
+}
+# fn main() {
+assert_eq!(check_all(&[(b"SETS", &[1, 0])]), Ok(1));
+assert_eq!(check_all(&[(b"SETS", &[1, 0]), (b"SETS", &[1])]).map_err(|e| e.statement_index), Err(1));
+# }
+```
+
+# Building arrays
+
+`set_array!` creates an array, runs the instructions of `set_slice!` on it, and produces it, \
+so an array can be initialized without a `let mut` and a separate `set_slice!`
+
+```ignore
+set_array! {
+    [T; N]:
+    [RANGE] = ...;                                   // any instruction on a range of the array
+    = ...;                                           // any instruction on the whole array
+    ...
+}
+```
+
+every element starts as `T::default()`, and `T` must implement `Default` \
+each instruction is written without its target, which is the new array, and works the same as in `set_slice!`, \
+including the line numbers in the panics
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let header = [0xca, 0xfe, 0xba, 0xbe];
+
+let packet = set_array! {
+    [u8; 10]:
+    [..4] = copy &header;
+    [4..8] = 1, 2, 3, 4;
+    [9] = 0xff;
+};
+
+assert_eq!(packet, [0xca, 0xfe, 0xba, 0xbe, 1, 2, 3, 4, 0, 0xff]);
+# }
+```
+
+# Debugging
+
+`set_slice_debug!` takes the same instructions as `set_slice!`, and names each list, `move`, `copy`, `clone`, `into`, \
+`to_owned`, `from_le` and `from_be` instruction after its own source, as if it had a `#[name = ...]`, \
+so a length mismatch panic shows the line and the instruction that failed, \
+e.g. `line 2 (body [1..] = copy &src): value length (1) is invalid, expected: 3` \
+the other instructions are run as they are, and a list of a single value that is not a literal is not named, \
+since it can not be told apart from the modes that take no value, like `default`
+
+```rust,should_panic
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let mut body = [0u8; 4];
+let src = [1u8];
+
+set_slice_debug! {
+    body = fill 0;
+    body[1..] = copy &src; // panics with `line 2 (body [1..] = copy &src): value length (1) is invalid, expected: 3`
+}
+# }
+```
+
+# Constant evaluation
+
+`const_set_slice!` takes the list, `copy` and `move` instructions of `set_slice!`, \
+and lowers them to index loops and panics with fixed messages, so it can be used in a `const fn` \
+the range of a target is written with literals or identifiers, like `buf[2..n]` or `buf[..=3]`, \
+other bounds must be put in parentheses, like `buf[(n + 1)..]` \
+`copy` takes a `&[T]` with `T: Copy`, and `move` assigns a whole value, so it does not take a range \
+`clone` is not supported, because `Clone::clone` can not be called in a `const fn`
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+const fn header(len: u8) -> [u8; 6] {
+    let mut buf = [0; 6];
+
+    const_set_slice! {
+        buf[..4] = copy b"SETS";
+        buf[4..] = len, 0;
+    }
+
+    buf
+}
+
+const HEADER: [u8; 6] = header(2);
+
+assert_eq!(HEADER, *b"SETS\x02\0");
+# }
+```
+
+# Reading slices
+
+`iter_slice!` is the read side of set_slice, it returns a lazy iterator over a range of a slice
+
+```ignore
+iter_slice!(SOURCE);                                 // iterate
+iter_slice!(SOURCE[RANGE]);                          // iterate range
+iter_slice!(SOURCE[RANGE] map FUNCTION);             // iterate range and map
+```
+
+the range is checked against the length of `SOURCE` when the macro runs, and the panic names the range \
+after that nothing is done until the iterator is used, and nothing is allocated \
+`FUNCTION: FnMut(&T) -> U` is applied to each element as it is yielded
+
+```rust
+# #[macro_use]
+# extern crate set_slice;
+# fn main() {
+let source = [1, 2, 3, 4, 5, 6];
+let mut doubled = iter_slice!(source[2..5] map |x| x * 2);
+
+assert_eq!(doubled.next(), Some(6));
+assert_eq!(doubled.next(), Some(8));
+assert_eq!(doubled.next(), Some(10));
+assert_eq!(doubled.next(), None);
+# }
+```
+
+# Cargo features
+This crate allows for use in no-std environment, and needs Rust 1.79 or newer.
+
+* `alloc` - enables the modes that need the `alloc` crate, such as `to_owned` and `runs`
+* `std` - enables `alloc`, and the debug build warnings of `truncate`, which are printed with `eprintln!`
+* `smallvec` - enables `alloc`, and lets `VEC = extend REFERENCE;` in `try_set_slice!` grow a `smallvec::SmallVec`
+* `heapless` - lets `VEC = extend REFERENCE;` in `try_set_slice!` grow a `heapless::Vec` up to its capacity, without `alloc`
+* `debug_checks` - turns the length checks into `debug_assert_eq!` and `debug_assert!`, so they are skipped in release builds
+
+**warning:** with `debug_checks`, a length mismatch is a bug that is no longer reported in release builds. \
+`copy` and `clone` still panic inside `copy_from_slice`, but the modes that zip the values stop at the shorter side. \
+the checks in front of unsafe code, those of `<=>`, `init` and `unsafe SLICE = ref REFERENCE;`, always run, \
+so no mode becomes unsound with this feature
+*/
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+
+#[doc(hidden)]
+pub use core::ptr::swap as __swap_ptr;
+#[doc(hidden)]
+pub use core::ptr::swap_nonoverlapping as __swap_nonoverlapping;
+#[doc(hidden)]
+pub use core::mem::size_of as __size_of;
+#[doc(hidden)]
+pub use core::ptr::copy_nonoverlapping as __copy_nonoverlapping;
+#[doc(hidden)]
+pub use core::ptr::write_volatile as __write_volatile;
+#[doc(hidden)]
+pub use core::ptr::drop_in_place as __drop_in_place;
+#[doc(hidden)]
+pub use core::mem::ManuallyDrop as __ManuallyDrop;
+#[doc(hidden)]
+pub use core::mem::MaybeUninit as __MaybeUninit;
+#[doc(hidden)]
+pub use core::ops::Add as __Add;
+#[doc(hidden)]
+pub use core::sync::atomic::{compiler_fence as __compiler_fence, fence as __fence, Ordering as __Ordering};
+#[doc(hidden)]
+pub use core::ops::BitXor as __BitXor;
+#[doc(hidden)]
+pub use core::f64::consts::PI as __PI;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::borrow::ToOwned as __ToOwned;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::vec::Vec as __Vec;
+
+/// the IEEE 802.3 CRC-32 of `data`, as big-endian bytes
+#[doc(hidden)]
+pub fn __checksum_crc32(data: &[u8]) -> [u8; 4] {
+    let mut crc = !0u32;
+
+    for &byte in data {
+        crc ^= u32::from(byte);
+
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+
+    (!crc).to_be_bytes()
+}
+
+/// the Fletcher-16 checksum of `data`, as big-endian bytes
+#[doc(hidden)]
+pub fn __checksum_fletcher16(data: &[u8]) -> [u8; 2] {
+    let (mut low, mut high) = (0u16, 0u16);
+
+    for &byte in data {
+        low = (low + u16::from(byte)) % 255;
+        high = (high + low) % 255;
+    }
+
+    [high as u8, low as u8]
+}
+
+/// the error produced by `try_set_slice!` when a value does not fit the slice it is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetSliceError {
+    /// the 1-based line of the instruction that failed, the same one a panic of `set_slice!` reports
+    pub line: usize,
+    /// the 0-based index of the instruction that failed, within its `try_set_slice!`
+    pub statement_index: usize,
+    /// why the instruction failed
+    pub kind: SetSliceErrorKind,
+    /// the length of the slice, or the capacity that was needed for `SetSliceErrorKind::Alloc`
+    pub expected: usize,
+    /// the length of the value, or the capacity that was available for `SetSliceErrorKind::Alloc`
+    pub actual: usize,
+}
+
+/// the reason that a `try_set_slice!` instruction failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetSliceErrorKind {
+    /// the value did not have the same length as the slice
+    Length,
+    /// the buffer could not allocate room for the value
+    Alloc,
+}
+
+impl core::fmt::Display for SetSliceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.kind {
+            SetSliceErrorKind::Length => write!(
+                f, "line {}: value length ({}) is invalid, expected: {}",
+                self.line, self.actual, self.expected
+            ),
+            SetSliceErrorKind::Alloc => write!(
+                f, "line {}: capacity ({}) could not be reserved, expected: {}",
+                self.line, self.actual, self.expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetSliceError {}
+
+/// the statistics of the values written by `with_stats`, see crate level docs for more info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliceStats<T> {
+    /// the smallest value written
+    pub min: T,
+    /// the largest value written
+    pub max: T,
+    /// the sum of all values written
+    pub sum: T,
+}
+
+/// the value of a list of a single reference, which is only valid if the slice holds references too,
+/// otherwise this bound fails with a hint to use `copy` or `clone`
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "Option is missing: a `{Self}` can not be stored in a slice of `{T}`",
+    label = "did you mean `copy` or `clone` before this reference?",
+    note = "use `copy REFERENCE` or `clone REFERENCE` to assign the values, or `move [REFERENCE]` to store the reference itself"
+)]
+pub trait __ListItem<T> {
+    fn __into_item(self) -> T;
+}
+
+impl<T> __ListItem<T> for T {
+    #[inline(always)]
+    fn __into_item(self) -> T {
+        self
+    }
+}
+
+/// never produced, this second impl only stops the compiler from taking the element type from the reference,
+/// so that the bound is checked against the element type of the slice
+#[doc(hidden)]
+pub enum __NotAListItem {}
+
+#[diagnostic::do_not_recommend]
+impl<T: ?Sized> __ListItem<__NotAListItem> for &T {
+    fn __into_item(self) -> __NotAListItem {
+        unreachable!()
+    }
+}
+
+/// panics if `slice` and `value` overlap, which safe code can not do, so this only catches aliasing made by unsafe code,
+/// `line` is the 1-based `LINE` of the instruction, `copy` and `clone` only call it in debug builds
+#[doc(hidden)]
+#[inline(always)]
+pub fn __check_overlap<T>(line: usize, slice: &[T], value: &[T]) {
+    let bytes = core::mem::size_of_val(slice);
+    let (start, value_start) = (slice.as_ptr() as usize, value.as_ptr() as usize);
+
+    assert!(
+        bytes == 0 || start + bytes <= value_start || value_start + bytes <= start,
+        "line {}: source and destination overlap", line
+    );
+}
+
+/// turns the length errors of `try_set_slice!` into its error type, which is only not `SetSliceError` with `try_fill_with`
+#[doc(hidden)]
+#[inline(always)]
+pub fn __into_error<E: From<SetSliceError>>(error: SetSliceError) -> E {
+    E::from(error)
+}
+
+/// checks that a value of length `actual` fits a slice of length `expected`,
+/// `line` is the 1-based `LINE` of the instruction
+#[doc(hidden)]
+pub fn __check_len(line: usize, expected: usize, actual: usize) -> Result<(), SetSliceError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(SetSliceError { line, statement_index: line - 1, kind: SetSliceErrorKind::Length, expected, actual })
+    }
+}
+
+/// the types of a slice and a value whose lengths are compared at compile time when both are arrays,
+/// `__check` is inherent for arrays, and falls back to the no-op `__RuntimeLen::__check` otherwise
+#[doc(hidden)]
+pub struct __LenProbe<S: ?Sized, V: ?Sized>(core::marker::PhantomData<(*const S, *const V)>);
+
+impl<S: ?Sized, V: ?Sized> __LenProbe<S, V> {
+    #[inline(always)]
+    pub fn new(_: &S, _: &V) -> Self {
+        __LenProbe(core::marker::PhantomData)
+    }
+}
+
+impl<T, U, const N: usize, const M: usize> __LenProbe<[T; N], [U; M]> {
+    /// `END` is `usize::MAX` for a range that is open at the end
+    #[inline(always)]
+    pub fn __check<const START: usize, const END: usize>(&self) {
+        const {
+            let end = if END == usize::MAX { N } else { END };
+
+            assert!(START <= end && end <= N, "set_slice: the range is out of bounds of the array");
+            assert!(end - START == M, "set_slice: the value length does not match the length of the range");
+        }
+    }
+}
+
+/// the fallback of `__LenProbe::__check`, when the lengths are only known at runtime
+#[doc(hidden)]
+pub trait __RuntimeLen {
+    #[inline(always)]
+    fn __check<const START: usize, const END: usize>(&self) {}
+}
+
+impl<S: ?Sized, V: ?Sized> __RuntimeLen for __LenProbe<S, V> {}
+
+/// the slice of an assignment target, so that anything that implements `AsMut<[T]>` can be assigned to,
+/// it is a method so that a `&mut [T]` target is reborrowed instead of borrowed mutably again
+#[doc(hidden)]
+pub trait __AsMutSlice<T> {
+    fn __as_mut_slice(&mut self) -> &mut [T];
+}
+
+impl<T, S: ?Sized + AsMut<[T]>> __AsMutSlice<T> for S {
+    #[inline(always)]
+    fn __as_mut_slice(&mut self) -> &mut [T] {
+        self.as_mut()
+    }
+}
+
+/// splits `slice` into the target of `range` and the remainder after its end,
+/// `range` is indexed first, so an out of bounds range panics like any other range
+#[doc(hidden)]
+#[inline(always)]
+pub fn __split_remainder<T, R>(slice: &mut [T], range: R) -> (&mut [T], &mut [T])
+where
+    R: core::ops::RangeBounds<usize> + core::slice::SliceIndex<[T], Output = [T]>,
+{
+    let start = match range.start_bound() {
+        core::ops::Bound::Included(&start) => start,
+        core::ops::Bound::Excluded(&start) => start + 1,
+        core::ops::Bound::Unbounded => 0,
+    };
+    let end = start + slice[range].len();
+    let (head, rest) = slice.split_at_mut(end);
+
+    (&mut head[start..], rest)
+}
+
+/// the start and end of `range` in a slice of length `len`, and whether it is open at the end,
+/// the bounds are not checked, and an inclusive end of `usize::MAX` saturates
+#[doc(hidden)]
+#[inline(always)]
+pub fn __range_bounds<R: core::ops::RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize, bool) {
+    use core::ops::Bound;
+
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+
+    match range.end_bound() {
+        Bound::Included(&end) => (start, end.saturating_add(1), false),
+        Bound::Excluded(&end) => (start, end, false),
+        Bound::Unbounded => (start, len, true),
+    }
+}
+
+/// the initialized elements of `slice`
+///
+/// # Safety
+///
+/// every element of `slice` must have been written
+#[doc(hidden)]
+#[inline(always)]
+pub unsafe fn __assume_init_mut<T>(slice: &mut [core::mem::MaybeUninit<T>]) -> &mut [T] {
+    // `MaybeUninit<T>` has the same layout as `T`
+    &mut *(slice as *mut [core::mem::MaybeUninit<T>] as *mut [T])
+}
+
+/// an array with every element set to `T::default()`, for any length
+#[doc(hidden)]
+#[inline(always)]
+pub fn __default_array<T: Default, const N: usize>() -> [T; N] {
+    core::array::from_fn(|_| T::default())
+}
+
+/// the element type of a cloned slice,
+/// `__clone_from_slice` is inherent for `Copy` types, and falls back to `__CloneFallback::__clone_from_slice` otherwise
+#[doc(hidden)]
+pub struct __CloneProbe<T>(core::marker::PhantomData<*const T>);
+
+impl<T> __CloneProbe<T> {
+    #[inline(always)]
+    pub fn new(_: &[T]) -> Self {
+        __CloneProbe(core::marker::PhantomData)
+    }
+}
+
+impl<T: Copy> __CloneProbe<T> {
+    #[inline(always)]
+    pub fn __clone_from_slice(&self, slice: &mut [T], value: &[T]) {
+        slice.copy_from_slice(value);
+    }
+}
+
+/// the fallback of `__CloneProbe::__clone_from_slice`, when the type is not known to be `Copy`
+#[doc(hidden)]
+pub trait __CloneFallback<T: Clone> {
+    #[inline(always)]
+    fn __clone_from_slice(&self, slice: &mut [T], value: &[T]) {
+        slice.clone_from_slice(value);
+    }
+}
+
+impl<T: Clone> __CloneFallback<T> for __CloneProbe<T> {}
+
+/// the growable buffers that `try_set_slice!` can extend, `Vec`, and `SmallVec` or `heapless::Vec` with their features
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+#[doc(hidden)]
+pub trait __TryExtend<T> {
+    fn __len(&self) -> usize;
+    fn __capacity(&self) -> usize;
+    /// returns `false` if the room could not be reserved
+    fn __try_reserve(&mut self, additional: usize) -> bool;
+    fn __extend_from_slice(&mut self, value: &[T]);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> __TryExtend<T> for alloc::vec::Vec<T> {
+    fn __len(&self) -> usize {
+        self.len()
+    }
+
+    fn __capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn __try_reserve(&mut self, additional: usize) -> bool {
+        self.try_reserve(additional).is_ok()
+    }
+
+    fn __extend_from_slice(&mut self, value: &[T]) {
+        self.extend_from_slice(value)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> __TryExtend<A::Item> for smallvec::SmallVec<A>
+where
+    A::Item: Clone,
+{
+    fn __len(&self) -> usize {
+        self.len()
+    }
+
+    fn __capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn __try_reserve(&mut self, additional: usize) -> bool {
+        self.try_reserve(additional).is_ok()
+    }
+
+    fn __extend_from_slice(&mut self, value: &[A::Item]) {
+        // `SmallVec::extend_from_slice` needs `Copy`, the room was already reserved, so this does not spill again
+        self.extend(value.iter().cloned())
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T: Clone, const N: usize> __TryExtend<T> for heapless::Vec<T, N> {
+    fn __len(&self) -> usize {
+        self.len()
+    }
+
+    fn __capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    // the capacity is fixed, so there is only room if the values already fit
+    fn __try_reserve(&mut self, additional: usize) -> bool {
+        additional <= N - self.len()
+    }
+
+    fn __extend_from_slice(&mut self, value: &[T]) {
+        let fits = self.extend_from_slice(value).is_ok();
+        debug_assert!(fits, "the room was checked by `__try_reserve`");
+    }
+}
+
+/// extends `vec` with `value`, reserving room for all of it first with `try_reserve`,
+/// `line` is the 1-based `LINE` of the instruction
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+#[doc(hidden)]
+pub fn __try_extend<T: Clone, V: __TryExtend<T>>(line: usize, vec: &mut V, value: &[T]) -> Result<(), SetSliceError> {
+    if vec.__try_reserve(value.len()) {
+        vec.__extend_from_slice(value);
+        Ok(())
+    } else {
+        Err(SetSliceError {
+            line,
+            statement_index: line - 1,
+            kind: SetSliceErrorKind::Alloc,
+            expected: vec.__len().saturating_add(value.len()),
+            actual: vec.__capacity(),
+        })
+    }
+}
+
+/// resolves `range` against a slice of length `len`,
+/// returns `None` if the range is out of bounds or decreasing
+#[doc(hidden)]
+pub fn __resolve_range<R: core::ops::RangeBounds<usize>>(range: R, len: usize) -> Option<(usize, usize)> {
+    use core::ops::Bound;
+
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start <= end && end <= len {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+/// the primitive integers, which can have their byte order reversed
+#[doc(hidden)]
+pub trait __SwapBytes: Copy {
+    fn __swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_swap_bytes {
+    ($($int:ty),*) => {$(
+        impl __SwapBytes for $int {
+            #[inline(always)]
+            fn __swap_bytes(self) -> Self {
+                self.swap_bytes()
+            }
+        }
+    )*};
+}
+
+impl_swap_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// warns that `line` cut off some of its values, see `truncate` in the crate level docs
+#[doc(hidden)]
+#[inline]
+pub fn __warn_truncated(line: usize, slice_len: usize, value_len: usize) {
+    #[cfg(all(feature = "std", debug_assertions))]
+    {
+        if value_len > slice_len {
+            std::eprintln!(
+                "set_slice: line {}: value length ({}) was truncated to the slice length ({})",
+                line, value_len, slice_len
+            );
+        }
+    }
+
+    let _ = (line, slice_len, value_len);
+}
+
+/// the primitive integers, which can be added without overflowing
+#[doc(hidden)]
+pub trait __Saturating: Copy {
+    fn __saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! impl_saturating {
+    ($($int:ty),*) => {$(
+        impl __Saturating for $int {
+            #[inline(always)]
+            fn __saturating_add(self, other: Self) -> Self {
+                self.saturating_add(other)
+            }
+        }
+    )*};
+}
+
+impl_saturating!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// the primitive integers, which can be added and multiplied with explicit overflow behaviour
+#[doc(hidden)]
+pub trait __Overflowing: Copy {
+    fn __wrapping_add(self, other: Self) -> Self;
+    fn __saturating_add(self, other: Self) -> Self;
+    fn __checked_add(self, other: Self) -> Option<Self>;
+    fn __wrapping_mul(self, other: Self) -> Self;
+    fn __saturating_mul(self, other: Self) -> Self;
+    fn __checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_overflowing {
+    ($($int:ty),*) => {$(
+        impl __Overflowing for $int {
+            #[inline(always)]
+            fn __wrapping_add(self, other: Self) -> Self {
+                self.wrapping_add(other)
+            }
+
+            #[inline(always)]
+            fn __saturating_add(self, other: Self) -> Self {
+                self.saturating_add(other)
+            }
+
+            #[inline(always)]
+            fn __checked_add(self, other: Self) -> Option<Self> {
+                self.checked_add(other)
+            }
+
+            #[inline(always)]
+            fn __wrapping_mul(self, other: Self) -> Self {
+                self.wrapping_mul(other)
+            }
+
+            #[inline(always)]
+            fn __saturating_mul(self, other: Self) -> Self {
+                self.saturating_mul(other)
+            }
+
+            #[inline(always)]
+            fn __checked_mul(self, other: Self) -> Option<Self> {
+                self.checked_mul(other)
+            }
+        }
+    )*};
+}
+
+impl_overflowing!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// the primitive floats, which can be converted to and from `f64`
+#[doc(hidden)]
+pub trait __Float: Copy {
+    fn __from_f64(value: f64) -> Self;
+
+    fn __to_f64(self) -> f64;
+}
+
+macro_rules! impl_float {
+    ($($float:ty),*) => {$(
+        impl __Float for $float {
+            #[inline(always)]
+            fn __from_f64(value: f64) -> Self {
+                value as $float
+            }
+
+            #[inline(always)]
+            fn __to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+/// the primitive integers, which can be written as bytes in any byte order
+#[doc(hidden)]
+pub trait __ToBytes: Copy {
+    type Bytes: AsRef<[u8]>;
+
+    fn __to_ne_bytes(self) -> Self::Bytes;
+    fn __to_le_bytes(self) -> Self::Bytes;
+    fn __to_be_bytes(self) -> Self::Bytes;
+}
+
+macro_rules! impl_to_bytes {
+    ($($int:ty),*) => {$(
+        impl __ToBytes for $int {
+            type Bytes = [u8; core::mem::size_of::<$int>()];
+
+            #[inline(always)]
+            fn __to_ne_bytes(self) -> Self::Bytes {
+                self.to_ne_bytes()
+            }
+
+            #[inline(always)]
+            fn __to_le_bytes(self) -> Self::Bytes {
+                self.to_le_bytes()
+            }
+
+            #[inline(always)]
+            fn __to_be_bytes(self) -> Self::Bytes {
+                self.to_be_bytes()
+            }
+        }
+    )*};
+}
+
+impl_to_bytes!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// copies `value` into `slice`, reversing the byte order of each element
+#[doc(hidden)]
+pub fn __copy_swapped<T: __SwapBytes>(slice: &mut [T], value: &[T]) {
+    for (item, value) in slice.iter_mut().zip(value) {
+        *item = value.__swap_bytes();
+    }
+}
+
+/// copies little-endian `value` into `slice` in native byte order
+#[cfg(target_endian = "little")]
+#[doc(hidden)]
+pub fn __copy_from_le<T: __SwapBytes>(slice: &mut [T], value: &[T]) {
+    slice.copy_from_slice(value);
+}
+
+/// copies little-endian `value` into `slice` in native byte order
+#[cfg(target_endian = "big")]
+#[doc(hidden)]
+pub fn __copy_from_le<T: __SwapBytes>(slice: &mut [T], value: &[T]) {
+    __copy_swapped(slice, value);
+}
+
+/// copies big-endian `value` into `slice` in native byte order
+#[cfg(target_endian = "little")]
+#[doc(hidden)]
+pub fn __copy_from_be<T: __SwapBytes>(slice: &mut [T], value: &[T]) {
+    __copy_swapped(slice, value);
+}
+
+/// copies big-endian `value` into `slice` in native byte order
+#[cfg(target_endian = "big")]
+#[doc(hidden)]
+pub fn __copy_from_be<T: __SwapBytes>(slice: &mut [T], value: &[T]) {
+    slice.copy_from_slice(value);
+}
+
+#[macro_export(local_inner_macros)]
+#[doc(hidden)]
+macro_rules! count {
+    ( )        => {0usize};
+    ($one:tt) => {1usize};
+    ($($pairs:tt $_p:tt)*) => {
+        count!($($pairs)*) << 1usize
+    };
+    ($odd:tt $($rest:tt)*) => {
+        count!($($rest)*) | 1usize
+    };
+}
+
+/// the length checks of `__set_slice_internals`, which are `debug_assert_eq!` with the `debug_checks` feature
+///
+/// only for checks whose failure can not cause undefined behavior, the checks in front of raw copies use `assert_eq!`
+#[cfg(not(feature = "debug_checks"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __assert_len_eq {
+    ($($args:tt)*) => { assert_eq!($($args)*) };
+}
+
+/// the length checks of `__set_slice_internals`, which are `debug_assert_eq!` with the `debug_checks` feature
+///
+/// only for checks whose failure can not cause undefined behavior, the checks in front of raw copies use `assert_eq!`
+#[cfg(feature = "debug_checks")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __assert_len_eq {
+    ($($args:tt)*) => { debug_assert_eq!($($args)*) };
+}
+
+/// the length checks of `__set_slice_internals` that are not a plain equality, which are `debug_assert!` with the `debug_checks` feature
+///
+/// only for checks whose failure can not cause undefined behavior, like `__assert_len_eq`
+#[cfg(not(feature = "debug_checks"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __assert_len {
+    ($($args:tt)*) => { assert!($($args)*) };
+}
+
+/// the length checks of `__set_slice_internals` that are not a plain equality, which are `debug_assert!` with the `debug_checks` feature
+///
+/// only for checks whose failure can not cause undefined behavior, like `__assert_len_eq`
+#[cfg(feature = "debug_checks")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __assert_len {
+    ($($args:tt)*) => { debug_assert!($($args)*) };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __as_mut_slice {
+    ($slice:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__AsMutSlice;
+
+        $slice.__as_mut_slice()
+    }};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __set_slice_internals {
+    (@line_range $($ln:tt),* => $slice:ident, ($d1:expr, $d2:expr), $x:expr, $y:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (d1, d2, x, y, len): (usize, usize, usize, usize, usize) = ($d1, $d2, $x, $y, $crate::__as_mut_slice!($slice).len());
+        let start = x.checked_mul(d1).and_then(|i| i.checked_add(y)).and_then(|i| i.checked_mul(d2));
+
+        match start.and_then(|start| Some((start, start.checked_add(d2)?))) {
+            Some((start, end)) if y < d1 && end <= len => start..end,
+            _ => panic!(
+                "line {}: index [{}, {}, ..] is out of bounds for dimensions (_, {}, {}) of length {}",
+                LINE, x, y, d1, d2, len
+            ),
+        }
+    }};
+
+    (@span_range $($ln:tt),* => $slice:ident, $offset:expr, $len:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (offset, len, sl): (usize, usize, usize) = ($offset, $len, $crate::__as_mut_slice!($slice).len());
+
+        match offset.checked_add(len) {
+            Some(end) if end <= sl => offset..end,
+            _ => panic!("line {}: span at({}, {}) is out of bounds for length {}", LINE, offset, len, sl),
+        }
+    }};
+
+    (@rect_bounds $($ln:tt),* => $slice:ident, $width:expr, ($x:expr, $y:expr, $w:expr, $h:expr)) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (width, x, y, w, h, len): (usize, usize, usize, usize, usize, usize) = ($width, $x, $y, $w, $h, $crate::__as_mut_slice!($slice).len());
+        let right = x.checked_add(w);
+        let end = y.checked_add(h).and_then(|bottom| bottom.checked_mul(width));
+
+        match (right, end) {
+            (Some(right), Some(end)) if right <= width && end <= len => (width, y * width + x, w, h),
+            _ => panic!(
+                "line {}: rect({}, {}, {}, {}) is out of bounds for width {} and length {}",
+                LINE, x, y, w, h, width, len
+            ),
+        }
+    }};
+
+    (@to_bytes be $value:expr) => {
+        $value.to_be_bytes()
+    };
+    (@to_bytes le $value:expr) => {
+        $value.to_le_bytes()
+    };
+    (@to_bytes $endian:ident $value:expr) => {
+        compile_error!(concat!("invalid byte order ", stringify!($endian), ", valid byte orders are be, le"))
+    };
+
+    (@from_bytes be $int:ident $bytes:expr) => {
+        $int::from_be_bytes($bytes)
+    };
+    (@from_bytes le $int:ident $bytes:expr) => {
+        $int::from_le_bytes($bytes)
+    };
+    (@from_bytes $endian:ident $int:ident $bytes:expr) => {
+        compile_error!(concat!("invalid byte order ", stringify!($endian), ", valid byte orders are be, le"))
+    };
+
+    (@step_slots $($ln:tt),* => $slice:expr, $step:expr, $vl:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (sl, step, vl): (usize, usize, usize) = ($slice.len(), $step, $vl);
+
+        assert!(step != 0, "line {}: step must be non-zero", LINE);
+
+        let slots = if sl == 0 { 0 } else { (sl - 1) / step + 1 };
+
+        $crate::__assert_len_eq!(slots, vl, "line {}: value length ({}) is invalid, expected: {} (the number of slots with step {})", LINE, vl, slots, step);
+
+        step
+    }};
+
+    (@element copy $value:expr) => {
+        *$value
+    };
+    (@element clone $value:expr) => {
+        Clone::clone($value)
+    };
+    (@element $option:ident $value:expr) => {
+        compile_error!(stringify!(invalid option $option, valid options are copy, clone))
+    };
+
+    (@fill_value Default) => {
+        Default::default()
+    };
+    (@fill_value $fill:expr) => {
+        Clone::clone(&$fill)
+    };
+
+    (@checksum crc32, $value:expr) => {
+        $crate::__checksum_crc32($value)
+    };
+    (@checksum fletcher16, $value:expr) => {
+        $crate::__checksum_fletcher16($value)
+    };
+    (@checksum $algorithm:ident, $value:expr) => {
+        compile_error!(concat!("invalid checksum ", stringify!($algorithm), ", valid checksums are crc32, fletcher16"))
+    };
+
+    (@in_range $min:literal ..= $max:literal, $value:expr) => {
+        $min <= $value && $value <= $max
+    };
+    (@in_range $min:literal .. $max:literal, $value:expr) => {
+        $min <= $value && $value < $max
+    };
+    (@in_range $min:literal $op:tt $max:literal, $value:expr) => {
+        compile_error!(concat!("invalid bounds ", stringify!($min $op $max), ", bounds must be of the form MIN..=MAX or MIN..MAX"))
+    };
+
+    (copy $slice:expr, $value:expr) => {
+        $slice.copy_from_slice($value);
+    };
+    (clone $slice:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__CloneFallback;
+
+        let slice: &mut [_] = &mut $slice[..];
+        let value: &[_] = &$value[..];
+
+        $crate::__CloneProbe::new(slice).__clone_from_slice(slice, value);
+    }};
+    (into $slice:expr, $value:expr) => {
+        for (item, value) in $slice.iter_mut().zip($value.iter()) {
+            *item = Clone::clone(value).into();
+        }
+    };
+    (from_le $slice:expr, $value:expr) => {
+        $crate::__copy_from_le($slice, $value);
+    };
+    (from_be $slice:expr, $value:expr) => {
+        $crate::__copy_from_be($slice, $value);
+    };
+    (to_owned $slice:expr, $value:expr) => {
+        for (item, value) in $slice.iter_mut().zip($value.iter()) {
+            *item = $crate::__ToOwned::to_owned(*value);
+        }
+    };
+    ($option:ident $slice:expr, $value:expr) => {
+        compile_error!(stringify!(invalid option $option, valid options are copy, clone, into, to_owned, from_le, from_be))
+    };
+
+    ($($ln:tt),* => move $slice:expr, $value:expr) => {
+        $crate::__set_slice_internals!(@named () $($ln),* => move $slice, $value)
+    };
+    (@named ($($name:expr)?) $($ln:tt),* => move $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T>(slice: &mut [T], value: &mut [T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, concat!("line {}", $(" (", $name, ")",)? ": value length ({}) is invalid, expected: {}"), LINE, vl, sl);
+            slice.swap_with_slice(value);
+        }
+
+        // capture value, the tokens of `$value` keep their spans, so borrow errors point at the caller's expression
+        let mut val = $value;
+        set($crate::__as_mut_slice!($slice), &mut val);
+    }};
+    ($($ln:tt),* => single_ref $slice:expr, $value:expr) => {{
+        #[inline(always)]
+        fn typed<T, V: $crate::__ListItem<T>>(slice: &mut [T], value: V) -> (&mut [T], T) {
+            (slice, value.__into_item())
+        }
+
+        let (slice, value) = typed($crate::__as_mut_slice!($slice), $value);
+        $crate::__set_slice_internals!($($ln),* => move slice, [value]);
+    }};
+    ($($ln:tt),* => $slice:expr, map $function:expr) => {{
+        #[inline(always)]
+        fn set<T: Copy, F: FnMut(T) -> T>(slice: &mut [T], mut function: F) {
+            for item in slice.iter_mut() {
+                *item = function(*item);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $function);
+    }};
+    ($($ln:tt),* => $slice:expr, push_back $value:expr) => {{
+        // the value is taken first, so that it may read the slice
+        let value = $value;
+        let slice = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+
+        if sl != 0 {
+            slice.copy_within(1.., 0);
+            slice[sl - 1] = value;
+        }
+    }};
+    ($($ln:tt),* => $slice:expr, push_front $value:expr) => {{
+        let value = $value;
+        let slice = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+
+        if sl != 0 {
+            slice.copy_within(..sl - 1, 1);
+            slice[0] = value;
+        }
+    }};
+    ($($ln:tt),* => try_fill_with $slice:expr, $factory:expr) => {{
+        #[inline(always)]
+        fn set<T, E, F: FnMut() -> Result<T, E>>(slice: &mut [T], mut factory: F) -> Result<(), E> {
+            for item in slice.iter_mut() {
+                *item = factory()?;
+            }
+
+            Ok(())
+        }
+
+        set($crate::__as_mut_slice!($slice), $factory)
+    }};
+    ($($ln:tt),* => $slice:expr, spread $value:expr) => {{
+        #[inline(always)]
+        fn set<T: Clone>(slice: &mut [T], value: T) {
+            if let Some((last, rest)) = slice.split_last_mut() {
+                for item in rest {
+                    item.clone_from(&value);
+                }
+
+                *last = value;
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value);
+    }};
+    ($($ln:tt),* => $slice:expr, fill $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => broadcast clone $slice, $value)
+    };
+    ($($ln:tt),* => $slice:expr, clone_fill $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => broadcast clone $slice, $value)
+    };
+    ($($ln:tt),* => $slice:expr, with $function:expr) => {{
+        #[inline(always)]
+        fn set<T, F: FnMut(usize) -> T>(slice: &mut [T], mut function: F) {
+            for (i, item) in slice.iter_mut().enumerate() {
+                *item = function(i);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $function);
+    }};
+    ($($ln:tt),* => $slice:expr, copy_fill $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => broadcast copy $slice, $value)
+    };
+    ($($ln:tt),* => $slice:expr, fill_copy $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => broadcast copy $slice, $value)
+    };
+    ($($ln:tt),* => default $slice:expr) => {
+        $crate::__set_slice_internals!($($ln),* => broadcast default $slice)
+    };
+    ($($ln:tt),* => broadcast $how:ident $slice:expr $(, $value:expr)?) => {{
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+
+        $crate::__set_slice_internals!(@broadcast $how slice $(, $value)?);
+    }};
+    (@broadcast clone $slice:ident, $value:expr) => {
+        $slice.fill($value)
+    };
+    (@broadcast copy $slice:ident, $value:expr) => {{
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], value: T) {
+            for item in slice {
+                *item = value;
+            }
+        }
+
+        set($slice, $value)
+    }};
+    (@broadcast default $slice:ident) => {
+        $slice.fill_with(Default::default)
+    };
+    ($($ln:tt),* => $slice:expr, move_default $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Default>(slice: &mut [T], value: &mut [T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+            slice.swap_with_slice(value);
+
+            for item in value {
+                *item = T::default();
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), &mut $value[..]);
+    }};
+    ($($ln:tt),* => $slice:expr, take_forget $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T>(slice: &mut [T], value: &mut [T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+            slice.swap_with_slice(value);
+
+            // safety: `value` now holds the old contents of the slice, and it is never used again
+            unsafe { $crate::__drop_in_place(value) }
+        }
+
+        let mut val = $crate::__ManuallyDrop::new($value); // capture value
+        set($crate::__as_mut_slice!($slice), &mut *val);
+    }};
+    ($($ln:tt),* => split $split:ident $slice:expr, $end:expr, $option:ident $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (end, rest) = match slice.$split() {
+            Some(split) => split,
+            None => panic!("line {}: slice length (0) is invalid, expected at least: 1", LINE),
+        };
+
+        *end = $end;
+        $crate::__set_slice_internals!($($ln),* => *rest, $option $value);
+    }};
+    ($($ln:tt),* => runs $out:expr, $source:expr, $predicate:expr, $summary:expr) => {{
+        #[inline(always)]
+        fn set<'a, T, U, P, F>(out: &mut $crate::__Vec<U>, mut source: &'a [T], mut predicate: P, mut summary: F)
+        where
+            P: FnMut(&T, &T) -> bool,
+            F: FnMut(&'a [T]) -> U,
+        {
+            out.clear();
+
+            while !source.is_empty() {
+                let mut len = 1;
+
+                while len < source.len() && predicate(&source[len - 1], &source[len]) {
+                    len += 1;
+                }
+
+                let (run, rest) = source.split_at(len);
+
+                out.push(summary(run));
+                source = rest;
+            }
+        }
+
+        set(&mut $out, &$source, $predicate, $summary);
+    }};
+    ($($ln:tt),* => $slice:expr, parity $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn parity<T: Copy + Default + $crate::__BitXor<Output = T>>(value: &[T]) -> T {
+            value.iter().fold(T::default(), |acc, &item| acc ^ item)
+        }
+
+        let value = parity($value);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+
+        $crate::__assert_len_eq!(sl, 1, "line {}: slice length ({}) is invalid, expected: 1", LINE, sl);
+        slice[0] = value;
+    }};
+    ($($ln:tt),* => pack $endian:ident $int:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        const WIDTH: usize = ($int::BITS / 8) as usize;
+        let value: &[$int] = $value;
+        let slice: &mut [u8] = $crate::__as_mut_slice!($slice);
+        let (sl, vl) = (slice.len(), value.len());
+
+        $crate::__assert_len_eq!(sl, vl * WIDTH, "line {}: slice length ({}) is invalid, expected: {} ({} bytes per value)", LINE, sl, vl * WIDTH, WIDTH);
+
+        for (bytes, value) in slice.chunks_exact_mut(WIDTH).zip(value) {
+            bytes.copy_from_slice(&$crate::__set_slice_internals!(@to_bytes $endian value));
+        }
+    }};
+    ($($ln:tt),* => from_bytes $endian:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [u8] = $crate::__as_mut_slice!($slice);
+        let bytes = $crate::__set_slice_internals!(@int_bytes $endian $value);
+        let bytes: &[u8] = bytes.as_ref();
+        let (sl, bl) = (slice.len(), bytes.len());
+
+        $crate::__assert_len_eq!(sl, bl, "line {}: slice length ({}) is invalid, expected: {} (the size of the value)", LINE, sl, bl);
+
+        slice.copy_from_slice(bytes);
+    }};
+    (@int_bytes ne $value:expr) => {
+        $crate::__ToBytes::__to_ne_bytes($value)
+    };
+    (@int_bytes le $value:expr) => {
+        $crate::__ToBytes::__to_le_bytes($value)
+    };
+    (@int_bytes be $value:expr) => {
+        $crate::__ToBytes::__to_be_bytes($value)
+    };
+    ($($ln:tt),* => unpack $endian:ident $int:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        const WIDTH: usize = ($int::BITS / 8) as usize;
+        let value: &[u8] = $value;
+        let slice: &mut [$int] = $crate::__as_mut_slice!($slice);
+        let (sl, vl) = (slice.len(), value.len());
+
+        $crate::__assert_len_eq!(vl, sl * WIDTH, "line {}: value length ({}) is invalid, expected: {} ({} bytes per value)", LINE, vl, sl * WIDTH, WIDTH);
+
+        for (item, bytes) in slice.iter_mut().zip(value.chunks_exact(WIDTH)) {
+            let mut buf = [0u8; WIDTH];
+
+            buf.copy_from_slice(bytes);
+            *item = $crate::__set_slice_internals!(@from_bytes $endian $int buf);
+        }
+    }};
+    ($($ln:tt),* => $slice:expr, transpose_bits8 $value:expr) => {{
+        #[inline(always)]
+        fn set(slice: &mut [u8; 8], value: &[u8; 8]) {
+            let mut x = u64::from_be_bytes(*value);
+
+            let t = (x ^ (x >> 7)) & 0x00aa_00aa_00aa_00aa;
+            x ^= t ^ (t << 7);
+            let t = (x ^ (x >> 14)) & 0x0000_cccc_0000_cccc;
+            x ^= t ^ (t << 14);
+            let t = (x ^ (x >> 28)) & 0x0000_0000_f0f0_f0f0;
+            x ^= t ^ (t << 28);
+
+            *slice = x.to_be_bytes();
+        }
+
+        set(&mut $slice, $value);
+    }};
+    ($($ln:tt),* => $slice:expr, extend $value:expr) => {
+        $slice.extend_from_slice($value);
+    };
+    ($($ln:tt),* => extend $option:ident $slice:expr, $value:expr) => {{
+        let vec = &mut $slice;
+        let value: &[_] = $value;
+        let len = vec.len().min(value.len());
+
+        vec.extend_from_slice(&value[len..]);
+        $crate::__set_slice_internals!($($ln),* => vec[..len], $option &value[..len]);
+    }};
+    ($($ln:tt),* => $slice:expr, arr_copy $value:expr) => {{
+        #[inline(always)]
+        fn set<T: Copy, const N: usize>(slice: &mut [T; N], value: &[T; N]) {
+            *slice = *value;
+        }
+
+        set(&mut $slice, $value);
+    }};
+    ($($ln:tt),* => swap $slice:expr, $other:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        /// `slice` must be valid for `sl` elements, and `other` for `ol` elements
+        #[inline(always)]
+        unsafe fn set<T>(slice: *mut T, sl: usize, other: *mut T, ol: usize) {
+            let bytes = sl * $crate::__size_of::<T>();
+            let (start, other_start) = (slice as usize, other as usize);
+
+            // these guard the swap below, so they stay on with `debug_checks`
+            assert!(sl == ol, "line {}: value length ({}) is invalid, expected: {}", LINE, ol, sl);
+            assert!(
+                bytes == 0 || start + bytes <= other_start || other_start + bytes <= start,
+                "line {}: the slices overlap, so they can not be swapped", LINE
+            );
+
+            // SAFETY: both pointers are valid for `sl` elements, as `ol == sl`, and the asserts above
+            // checked that the two ranges do not overlap
+            unsafe { $crate::__swap_nonoverlapping(slice, other, sl) }
+        }
+
+        // the slices are turned into pointers one after the other, so that they may come from the same buffer
+        let (slice, sl) = { let slice: &mut [_] = $crate::__as_mut_slice!($slice); (slice.as_mut_ptr(), slice.len()) };
+        let (other, ol) = { let other: &mut [_] = &mut $other; (other.as_mut_ptr(), other.len()) };
+
+        // SAFETY: the pointers and lengths were just taken from two live slices
+        unsafe { set(slice, sl, other, ol) }
+    }};
+    ($($ln:tt),* => col copy $grid:expr, $col:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy, R: AsMut<[T]>>(grid: &mut [R], col: usize, value: &[T]) {
+            let (gl, vl) = (grid.len(), value.len());
+
+            $crate::__assert_len_eq!(gl, vl, "line {}: value length ({}) is invalid, expected: {} (the number of rows)", LINE, vl, gl);
+
+            for (row, &value) in grid.iter_mut().zip(value) {
+                row.as_mut()[col] = value;
+            }
+        }
+
+        set(&mut $grid, $col, $value);
+    }};
+    ($($ln:tt),* => grid $option:ident $grid:expr, $cols:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        let grid: &mut [_] = $grid;
+        let cols = $cols;
+        let value: &[_] = $value;
+        let width = match grid.first_mut() {
+            Some(row) => $crate::__as_mut_slice!(row)[cols.clone()].len(),
+            None => 0,
+        };
+
+        for (r, row) in grid.iter_mut().enumerate() {
+            let span = $crate::__as_mut_slice!(row)[cols.clone()].len();
+
+            $crate::__assert_len_eq!(span, width, "line {}: column span ({}) of row {} is invalid, expected: {}", LINE, span, r, width);
+        }
+
+        let (rows, vl) = (grid.len(), value.len());
+
+        $crate::__assert_len_eq!(vl, rows * width, "line {}: value length ({}) is invalid, expected: {} ({} rows of {})", LINE, vl, rows * width, rows, width);
+
+        for (row, chunk) in grid.iter_mut().zip(value.chunks(width.max(1))) {
+            let target = &mut $crate::__as_mut_slice!(row)[cols.clone()];
+
+            $crate::__set_slice_internals!($option (*target), chunk);
+        }
+    }};
+    ($($ln:tt),* => copy_field $slice:expr, $field:tt, $value:expr, $value_field:tt) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = &$value;
+        let (sl, vl) = (slice.len(), value.len());
+
+        $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+        for (item, value) in slice.iter_mut().zip(value) {
+            item.$field = value.$value_field;
+        }
+    }};
+    ($($ln:tt),* => $slice:expr, iter $iter:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T, I: IntoIterator<Item = T>>(slice: &mut [T], iter: I) {
+            let sl = slice.len();
+            let mut iter = iter.into_iter();
+
+            for (i, item) in slice.iter_mut().enumerate() {
+                match iter.next() {
+                    Some(value) => *item = value,
+                    None => panic!("line {}: iterator length ({}) is invalid, expected: {}", LINE, i, sl),
+                }
+            }
+
+            assert!(iter.next().is_none(), "line {}: iterator length (more than {}) is invalid, expected: {}", LINE, sl, sl);
+        }
+
+        set($crate::__as_mut_slice!($slice), $iter);
+    }};
+    ($($ln:tt),* => $slice:expr, iter_ref $iter:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<'a, T: Clone + 'a, I: IntoIterator<Item = &'a T>>(slice: &mut [T], iter: I) {
+            let sl = slice.len();
+            let mut iter = iter.into_iter();
+
+            for (i, item) in slice.iter_mut().enumerate() {
+                match iter.next() {
+                    Some(value) => item.clone_from(value),
+                    None => panic!("line {}: iterator length ({}) is invalid, expected: {}", LINE, i, sl),
+                }
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $iter);
+    }};
+    ($($ln:tt),* => $slice:expr, from_deque $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => from_deque copy $slice, $value)
+    };
+    ($($ln:tt),* => $slice:expr, $option:ident $source:ident[min = $len:expr]) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let source: &[_] = &$source;
+        let (vl, len) = (source.len(), $len);
+
+        $crate::__assert_len!(vl >= len, "line {}: value length ({}) is invalid, expected at least: {}", LINE, vl, len);
+
+        let input = &source[..len];
+
+        $crate::__set_slice_internals!($($ln),* => $slice, $option input);
+    }};
+    ($($ln:tt),* => $slice:expr, $option:ident $source:ident[max = $len:expr]) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let source: &[_] = &$source;
+        let (vl, len) = (source.len(), $len);
+
+        $crate::__assert_len!(vl <= len, "line {}: value length ({}) is invalid, expected at most: {}", LINE, vl, len);
+
+        let slice = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+
+        $crate::__assert_len!(sl >= vl, "line {}: slice length ({}) is invalid, expected at least: {}", LINE, sl, vl);
+
+        $crate::__set_slice_internals!($($ln),* => slice[..vl], $option source);
+    }};
+    ($($ln:tt),* => $slice:expr, $option:ident $source:ident[$($range:tt)*]) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let source: &[_] = &$source;
+        let (start, end) = match $crate::__resolve_range($($range)*, source.len()) {
+            Some(bounds) => bounds,
+            None => panic!(
+                "line {}: source range {}[{}] is out of bounds for length {}",
+                LINE, stringify!($source), stringify!($($range)*), source.len()
+            ),
+        };
+        let input = &source[start..end];
+
+        $crate::__set_slice_internals!($($ln),* => $slice, $option input);
+    }};
+    ($($ln:tt),* => $slice:expr, $option:ident $value:expr) => {
+        $crate::__set_slice_internals!(@named () $($ln),* => $slice, $option $value)
+    };
+    (@named ($($name:expr)?) $($ln:tt),* => $slice:expr, $option:ident $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let input: &_ = $value;
+        let slice = &mut $slice;
+        $crate::__set_slice_internals!(@const_len &*slice, input, 0, usize::MAX);
+        let slice = $crate::__as_mut_slice!(slice);
+        let (il, sl) = (input.len(), slice.len());
+
+        $crate::__assert_len_eq!(il, sl, concat!("line {}", $(" (", $name, ")",)? ": value length ({}) is invalid, expected: {}"), LINE, il, sl);
+
+        $crate::__set_slice_internals!(@overlap $option LINE, slice, input);
+        $crate::__set_slice_internals!($option slice, input);
+    }};
+    (@overlap copy $line:expr, $slice:expr, $value:expr) => {
+        if cfg!(debug_assertions) {
+            $crate::__check_overlap($line, $slice, &$value[..]);
+        }
+    };
+    (@overlap clone $line:expr, $slice:expr, $value:expr) => {
+        if cfg!(debug_assertions) {
+            $crate::__check_overlap($line, $slice, &$value[..]);
+        }
+    };
+    (@overlap $option:ident $line:expr, $slice:expr, $value:expr) => {};
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, copy $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, copy $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, clone $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, clone $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, into $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, into $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, to_owned $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, to_owned $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, from_le $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, from_le $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, $range:tt, $bounds:tt, from_be $value:expr) => {
+        $crate::__set_slice_internals!(@const_range $($ln),* => $slice, $range, $bounds, from_be $value)
+    };
+    ($($ln:tt),* => const_range $slice:ident, ($($range:tt)*), $bounds:tt, $option:ident $value:expr) => {
+        $crate::__set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $value)
+    };
+    (@const_range $($ln:tt),* => $slice:ident, ($($range:tt)*), ($start:expr, $end:expr), $option:ident $value:expr) => {{
+        let input: &_ = $value;
+        $crate::__set_slice_internals!(@const_len &$slice, input, $start, $end);
+        $crate::__set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option input)
+    }};
+    ($($ln:tt),* => const_list $slice:ident, ($($range:tt)*), ($start:expr, $end:expr), [$($value:expr),+]) => {{
+        const _: () = assert!(
+            $end == usize::MAX || ($start <= $end && $end - $start == $crate::count!($($value)*)),
+            "set_slice: the number of values in the list does not match the length of the range"
+        );
+
+        let input = [$($value),+];
+        $crate::__set_slice_internals!(@const_len &$slice, &input, $start, $end);
+        $crate::__set_slice_internals!($($ln),* => move $crate::__as_mut_slice!($slice)[$($range)*], input)
+    }};
+    (@const_len $slice:expr, $value:expr, $start:expr, $end:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__RuntimeLen;
+
+        $crate::__LenProbe::new($slice, $value).__check::<{ $start }, { $end }>();
+    }};
+    ($($ln:tt),* => ref $slice:expr, $size:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        
+        #[inline(always)]
+        fn set<T>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            // these guard the raw copy below, so they stay on with `debug_checks`
+            assert_eq!(sl, $size, "line {}: slice length ({}) is invalid, expected: {}", LINE, sl, $size);
+            assert_eq!(vl, $size, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, $size);
+            
+            unsafe {
+                $crate::__drop_in_place(slice as *mut [T]);
+                $crate::__copy_nonoverlapping(value.as_ptr(), slice.as_mut_ptr(), $size);
+            }
+        }
+
+        let input: &_ = $value;
+        let slice = $crate::__as_mut_slice!($slice);
+
+        set(slice, input);
+    }};
+    ($($ln:tt),* => ref $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            // this guards the raw copy below, so it stays on with `debug_checks`
+            assert_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            unsafe {
+                $crate::__drop_in_place(slice as *mut [T]);
+                $crate::__copy_nonoverlapping(value.as_ptr(), slice.as_mut_ptr(), sl);
+            }
+        }
+
+        let input: &_ = $value;
+        let slice = $crate::__as_mut_slice!($slice);
+
+        set(slice, input);
+    }};
+    ($($ln:tt),* => checked const $slice:expr, $min:literal $op:tt $max:literal, $($value:literal),+) => {{
+        $(
+            #[allow(unused_comparisons)]
+            const _: () = assert!(
+                $crate::__set_slice_internals!(@in_range $min $op $max, $value),
+                concat!("value ", stringify!($value), " is out of range ", stringify!($min), stringify!($op), stringify!($max))
+            );
+        )+
+
+        $crate::__set_slice_internals!($($ln),* => move $slice, [$($value),+]);
+    }};
+    ($($ln:tt),* => checked $slice:expr, $min:literal $op:tt $max:literal, $($value:expr),+) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let value = [$($value),+];
+
+        for (i, item) in value.iter().enumerate() {
+            #[allow(unused_comparisons)]
+            let in_range = $crate::__set_slice_internals!(@in_range $min $op $max, *item);
+
+            debug_assert!(
+                in_range, "line {}: value {} of the list is out of range {}",
+                LINE, i, concat!(stringify!($min), stringify!($op), stringify!($max))
+            );
+        }
+
+        $crate::__set_slice_internals!($($ln),* => move $slice, value);
+    }};
+    ($($ln:tt),* => volatile copy $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            for (item, value) in slice.iter_mut().zip(value) {
+                // safety: `item` is a valid, aligned, and unique pointer to a `T`
+                unsafe { $crate::__write_volatile(item, *value) }
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value);
+    }};
+    ($($ln:tt),* => truncate $option:ident $slice:expr, $value:expr) => {
+        $crate::__set_slice_internals!(@truncate true $($ln),* => $option $slice, $value)
+    };
+    ($($ln:tt),* => saturating $option:ident $slice:expr, $value:expr) => {
+        $crate::__set_slice_internals!(@truncate false $($ln),* => $option $slice, $value)
+    };
+    // `saturating` is `truncate` without the warning
+    (@truncate $warn:tt $($ln:tt),* => $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let input: &[_] = $value;
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (il, sl) = (input.len(), slice.len());
+        let len = if il < sl { il } else { sl };
+
+        if $warn {
+            $crate::__warn_truncated(LINE, sl, il);
+        }
+        $crate::__set_slice_internals!($option slice[..len], &input[..len]);
+        len
+    }};
+    ($($ln:tt),* => saturating_add copy $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: $crate::__Saturating>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            for (item, &value) in slice.iter_mut().zip(value) {
+                *item = item.__saturating_add(value);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value);
+    }};
+    ($($ln:tt),* => compound $overflow:ident $op:tt copy $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        #[allow(unused_variables)] // the index is only used by `checked`
+        fn set<T: $crate::__Overflowing>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            for (i, (item, &value)) in slice.iter_mut().zip(value).enumerate() {
+                *item = $crate::__set_slice_internals!(@compound $overflow $op LINE, i, *item, value);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value);
+    }};
+    (@compound wrapping += $line:ident, $i:ident, $a:expr, $b:expr) => { $a.__wrapping_add($b) };
+    (@compound saturating += $line:ident, $i:ident, $a:expr, $b:expr) => { $a.__saturating_add($b) };
+    (@compound checked += $line:ident, $i:ident, $a:expr, $b:expr) => {
+        match $a.__checked_add($b) {
+            Some(sum) => sum,
+            None => panic!("line {}: addition overflowed at index {}", $line, $i),
+        }
+    };
+    (@compound wrapping *= $line:ident, $i:ident, $a:expr, $b:expr) => { $a.__wrapping_mul($b) };
+    (@compound saturating *= $line:ident, $i:ident, $a:expr, $b:expr) => { $a.__saturating_mul($b) };
+    (@compound checked *= $line:ident, $i:ident, $a:expr, $b:expr) => {
+        match $a.__checked_mul($b) {
+            Some(product) => product,
+            None => panic!("line {}: multiplication overflowed at index {}", $line, $i),
+        }
+    };
+    (@compound $overflow:ident $op:tt $line:ident, $i:ident, $a:expr, $b:expr) => {
+        compile_error!(concat!(
+            "invalid compound assignment =", stringify!($overflow), stringify!($op),
+            ", valid overflow behaviours are wrapping, saturating, checked, and valid operators are +=, *="
+        ))
+    };
+    ($($ln:tt),* => add_unique $slice:expr, $len:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: PartialEq + Clone>(slice: &mut [T], mut len: usize, value: &[T]) -> usize {
+            let sl = slice.len();
+
+            assert!(len <= sl, "line {}: set length ({}) is invalid, expected at most: {}", LINE, len, sl);
+
+            for item in value {
+                if !slice[..len].contains(item) {
+                    assert!(len < sl, "line {}: the set is full ({} elements), expected room for another value", LINE, sl);
+                    slice[len] = item.clone();
+                    len += 1;
+                }
+            }
+
+            len
+        }
+
+        set($crate::__as_mut_slice!($slice), $len, $value)
+    }};
+    ($($ln:tt),* => dedup_into $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: PartialEq + Copy>(slice: &mut [T], value: &[T]) -> usize {
+            let count = match value.split_first() {
+                Some((_, rest)) => 1 + value.iter().zip(rest).filter(|&(a, b)| a != b).count(),
+                None => 0,
+            };
+            let sl = slice.len();
+
+            assert!(sl >= count, "line {}: slice length ({}) is invalid, expected at least: {}", LINE, sl, count);
+
+            let mut written = 0;
+
+            for item in value {
+                if written == 0 || slice[written - 1] != *item {
+                    slice[written] = *item;
+                    written += 1;
+                }
+            }
+
+            written
+        }
+
+        set($crate::__as_mut_slice!($slice), $value)
+    }};
+    ($($ln:tt),* => copy_until $slice:expr, $sentinel:expr, $truncate:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: PartialEq + Copy>(slice: &mut [T], sentinel: T, truncate: bool, value: &[T]) -> usize {
+            let len = value.iter().position(|item| *item == sentinel).unwrap_or(value.len());
+            let sl = slice.len();
+
+            let len = if len <= sl || !truncate {
+                assert!(sl >= len, "line {}: slice length ({}) is invalid, expected at least: {}", LINE, sl, len);
+                len
+            } else {
+                sl
+            };
+
+            slice[..len].copy_from_slice(&value[..len]);
+
+            len
+        }
+
+        set($crate::__as_mut_slice!($slice), $sentinel, $truncate, $value)
+    }};
+    ($($ln:tt),* => checksum $algorithm:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let checksum = $crate::__set_slice_internals!(@checksum $algorithm, $value);
+        let slice: &mut [u8] = $crate::__as_mut_slice!($slice);
+        let (sl, cl) = (slice.len(), checksum.len());
+
+        $crate::__assert_len_eq!(sl, cl, "line {}: slice length ({}) is invalid, expected: {} (the width of {})", LINE, sl, cl, stringify!($algorithm));
+        slice.copy_from_slice(&checksum);
+    }};
+    ($($ln:tt),* => variant_table $slice:expr, ($($fill:tt)*), { $($variant:path => $value:expr),* }) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+
+        for item in slice.iter_mut() {
+            *item = $crate::__set_slice_internals!(@fill_value $($fill)*);
+        }
+
+        $({
+            let index = $variant as usize;
+
+            assert!(index < sl, "line {}: variant {} ({}) is out of bounds for length {}", LINE, stringify!($variant), index, sl);
+            slice[index] = $value;
+        })*
+    }};
+    ($($ln:tt),* => some_all $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let input: &[_] = $value;
+        let slice: &mut [Option<_>] = $crate::__as_mut_slice!($slice);
+        let (il, sl) = (input.len(), slice.len());
+
+        $crate::__assert_len_eq!(il, sl, "line {}: value length ({}) is invalid, expected: {}", LINE, il, sl);
+
+        for (item, value) in slice.iter_mut().zip(input) {
+            *item = Some($crate::__set_slice_internals!(@element $option value));
+        }
+    }};
+    ($($ln:tt),* => cursor write $slice:ident, $cursor:ident, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let value: &[_] = $value;
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (at, vl, sl) = ($cursor, value.len(), slice.len());
+
+        match at.checked_add(vl) {
+            Some(end) if end <= sl => {
+                slice[at..end].copy_from_slice(value);
+                $cursor = end;
+            }
+            _ => panic!("line {}: write of length {} at cursor {} is out of bounds for length {}", LINE, vl, at, sl),
+        }
+    }};
+    ($($ln:tt),* => rect fill $slice:ident, $width:expr, $rect:tt, $value:expr) => {{
+        let (width, start, w, h) = $crate::__set_slice_internals!(@rect_bounds $($ln),* => $slice, $width, $rect);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value = $value;
+
+        for row in 0..h {
+            for item in &mut slice[start + row * width..][..w] {
+                item.clone_from(&value);
+            }
+        }
+    }};
+    ($($ln:tt),* => rect copy $slice:ident, $width:expr, $rect:tt, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (width, start, w, h) = $crate::__set_slice_internals!(@rect_bounds $($ln),* => $slice, $width, $rect);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = $value;
+        let vl = value.len();
+
+        $crate::__assert_len_eq!(vl, w * h, "line {}: value length ({}) is invalid, expected: {} (W * H)", LINE, vl, w * h);
+
+        if w > 0 {
+            for (row, value) in value.chunks_exact(w).enumerate() {
+                slice[start + row * width..][..w].copy_from_slice(value);
+            }
+        }
+    }};
+    ($($ln:tt),* => masked fill $invert:expr, $slice:expr, $mask:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Clone>(slice: &mut [T], mask: &[bool], invert: bool, value: T) -> usize {
+            let (sl, ml) = (slice.len(), mask.len());
+
+            $crate::__assert_len_eq!(ml, sl, "line {}: mask length ({}) is invalid, expected: {}", LINE, ml, sl);
+
+            let mut count = 0;
+
+            for (item, &selected) in slice.iter_mut().zip(mask) {
+                if selected != invert {
+                    item.clone_from(&value);
+                    count += 1;
+                }
+            }
+
+            count
+        }
+
+        set($crate::__as_mut_slice!($slice), &$mask, $invert, $value)
+    }};
+    ($($ln:tt),* => masked copy $invert:expr, $slice:expr, $mask:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], mask: &[bool], invert: bool, value: &[T]) -> usize {
+            let (sl, ml) = (slice.len(), mask.len());
+
+            $crate::__assert_len_eq!(ml, sl, "line {}: mask length ({}) is invalid, expected: {}", LINE, ml, sl);
+
+            let count = mask.iter().filter(|&&selected| selected != invert).count();
+            let vl = value.len();
+
+            $crate::__assert_len_eq!(vl, count, "line {}: value length ({}) is invalid, expected: {} (the number of masked elements)", LINE, vl, count);
+
+            let selected = slice.iter_mut().zip(mask).filter(|&(_, &selected)| selected != invert);
+
+            for ((item, _), value) in selected.zip(value) {
+                *item = *value;
+            }
+
+            count
+        }
+
+        set($crate::__as_mut_slice!($slice), &$mask, $invert, $value)
+    }};
+    ($($ln:tt),* => with_stats copy $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T>(slice: &mut [T], value: &[T]) -> Option<$crate::SliceStats<T>>
+        where
+            T: Ord + Copy + $crate::__Add<Output = T>
+        {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            let mut stats: Option<$crate::SliceStats<T>> = None;
+
+            for (item, &value) in slice.iter_mut().zip(value) {
+                *item = value;
+
+                stats = Some(match stats {
+                    Some(stats) => $crate::SliceStats {
+                        min: stats.min.min(value),
+                        max: stats.max.max(value),
+                        sum: stats.sum + value,
+                    },
+                    None => $crate::SliceStats { min: value, max: value, sum: value },
+                });
+            }
+
+            stats
+        }
+
+        set($crate::__as_mut_slice!($slice), $value)
+    }};
+    ($($ln:tt),* => diff_log copy $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: PartialEq + Copy>(slice: &mut [T], value: &[T]) -> $crate::__Vec<(usize, T, T)> {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            let mut log = $crate::__Vec::new();
+
+            for (i, (item, &value)) in slice.iter_mut().zip(value).enumerate() {
+                if *item != value {
+                    log.push((i, *item, value));
+                    *item = value;
+                }
+            }
+
+            log
+        }
+
+        set($crate::__as_mut_slice!($slice), $value)
+    }};
+    ($($ln:tt),* => from_deque $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let (front, back) = $value.as_slices();
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (il, sl) = (front.len() + back.len(), slice.len());
+
+        $crate::__assert_len_eq!(il, sl, "line {}: value length ({}) is invalid, expected: {}", LINE, il, sl);
+
+        let (head, tail) = slice.split_at_mut(front.len());
+
+        $crate::__set_slice_internals!($option head, front);
+        $crate::__set_slice_internals!($option tail, back);
+    }};
+    ($($ln:tt),* => none_all $slice:expr) => {{
+        let slice: &mut [Option<_>] = $crate::__as_mut_slice!($slice);
+
+        for item in slice.iter_mut() {
+            *item = None;
+        }
+    }};
+    ($($ln:tt),* => ramp $slice:expr, $start:expr, $end:expr) => {{
+        #[inline(always)]
+        fn set<T: $crate::__Float>(slice: &mut [T], start: T, end: T) {
+            let (start, end) = (start.__to_f64(), end.__to_f64());
+            let steps = if slice.len() > 1 { (slice.len() - 1) as f64 } else { 1.0 };
+
+            for (i, item) in slice.iter_mut().enumerate() {
+                *item = T::__from_f64(start + (end - start) * (i as f64 / steps));
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $start, $end);
+    }};
+    ($($ln:tt),* => window $window:ident $slice:expr) => {{
+        #[inline(always)]
+        fn set<T: $crate::__Float>(slice: &mut [T]) {
+            if slice.len() <= 1 {
+                for item in slice.iter_mut() {
+                    *item = T::__from_f64(1.0);
+                }
+
+                return;
+            }
+
+            let steps = (slice.len() - 1) as f64;
+
+            for (i, item) in slice.iter_mut().enumerate() {
+                let x: f64 = 2.0 * $crate::__PI * (i as f64 / steps);
+
+                *item = T::__from_f64($crate::__set_slice_internals!(@window $window x));
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice));
+    }};
+    (@window hann $x:ident) => { 0.5 - 0.5 * $x.cos() };
+    (@window hamming $x:ident) => { 0.54 - 0.46 * $x.cos() };
+    (@window blackman $x:ident) => { 0.42 - 0.5 * $x.cos() + 0.08 * (2.0 * $x).cos() };
+    (@window $window:ident $x:ident) => {
+        compile_error!(concat!("unknown window `", stringify!($window), "`, expected one of: hann, hamming, blackman"))
+    };
+    ($($ln:tt),* => normalize $checked:expr, $norm:ident $slice:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: $crate::__Float>(slice: &mut [T], checked: bool) {
+            let total: f64 = $crate::__set_slice_internals!(@normalize $norm slice.iter().map(|&value| value.__to_f64()));
+
+            if total != 0.0 && total.is_finite() {
+                for item in slice.iter_mut() {
+                    *item = T::__from_f64(item.__to_f64() / total);
+                }
+            } else if checked {
+                panic!("line {}: the {} ({}) is invalid, expected a finite non-zero total", LINE, stringify!($norm), total);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $checked);
+    }};
+    (@normalize sum $values:expr) => { $values.sum() };
+    (@normalize max $values:expr) => { $values.fold(f64::NEG_INFINITY, f64::max) };
+    (@normalize $norm:ident $values:expr) => {
+        compile_error!(concat!("invalid norm ", stringify!($norm), ", valid norms are sum, max"))
+    };
+    ($($ln:tt),* => clamp_copy $slice:expr, $value:expr, $low:expr, $high:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Ord + Copy>(slice: &mut [T], value: &[T], low: &[T], high: &[T]) {
+            let sl = slice.len();
+
+            for &(name, len) in &[("value", value.len()), ("low bound", low.len()), ("high bound", high.len())] {
+                $crate::__assert_len_eq!(len, sl, "line {}: {} length ({}) is invalid, expected: {}", LINE, name, len, sl);
+            }
+
+            for (((item, &value), &low), &high) in slice.iter_mut().zip(value).zip(low).zip(high) {
+                *item = value.clamp(low, high);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value, $low, $high);
+    }};
+    ($($ln:tt),* => merge $slice:expr, $left:expr, $right:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Ord + Copy>(slice: &mut [T], left: &[T], right: &[T]) {
+            let (sl, vl) = (slice.len(), left.len() + right.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: slice length ({}) is invalid, expected: {} (the combined length of both values)", LINE, sl, vl);
+
+            let (mut l, mut r) = (0, 0);
+
+            for item in slice.iter_mut() {
+                if r == right.len() || (l < left.len() && left[l] <= right[r]) {
+                    *item = left[l];
+                    l += 1;
+                } else {
+                    *item = right[r];
+                    r += 1;
+                }
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $left, $right);
+    }};
+    ($($ln:tt),* => fma $slice:expr, $a:expr, $b:expr, $scalar:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn check<'a, T>(slice: &'a mut [T], a: &'a [T], b: &'a [T]) -> (&'a mut [T], &'a [T], &'a [T]) {
+            let (sl, al, bl) = (slice.len(), a.len(), b.len());
+
+            $crate::__assert_len!(al == sl && bl == sl, "line {}: value lengths ({}, {}) are invalid, expected: {}", LINE, al, bl, sl);
+            (slice, a, b)
+        }
+
+        let (slice, a, b) = check($crate::__as_mut_slice!($slice), $a, $b);
+        let scalar = $scalar;
+
+        for ((item, &a), &b) in slice.iter_mut().zip(a).zip(b) {
+            *item = a.mul_add(scalar, b);
+        }
+    }};
+    ($($ln:tt),* => fma $slice:expr, $a:expr, $b:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn check<'a, T>(slice: &'a mut [T], a: &'a [T], b: &'a [T]) -> (&'a mut [T], &'a [T], &'a [T]) {
+            let (sl, al, bl) = (slice.len(), a.len(), b.len());
+
+            $crate::__assert_len!(al == sl && bl == sl, "line {}: value lengths ({}, {}) are invalid, expected: {}", LINE, al, bl, sl);
+            (slice, a, b)
+        }
+
+        let (slice, a, b) = check($crate::__as_mut_slice!($slice), $a, $b);
+
+        for ((item, &a), &b) in slice.iter_mut().zip(a).zip(b) {
+            *item = a.mul_add(b, *item);
+        }
+    }};
+    ($($ln:tt),* => permute $option:ident $slice:expr, $perm:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (perm, value): (&[usize], &[_]) = ($perm, $value);
+        let (sl, pl, vl) = (slice.len(), perm.len(), value.len());
+
+        $crate::__assert_len_eq!(pl, sl, "line {}: permutation length ({}) is invalid, expected: {}", LINE, pl, sl);
+
+        if let Some(index) = perm.iter().position(|&index| index >= vl) {
+            panic!("line {}: permutation index perm[{}] ({}) is out of bounds for length {}", LINE, index, perm[index], vl);
+        }
+
+        for (item, &index) in slice.iter_mut().zip(perm) {
+            *item = $crate::__set_slice_internals!(@element $option (&value[index]));
+        }
+    }};
+    ($($ln:tt),* => scatter_permute $option:ident $slice:expr, $perm:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (perm, value): (&[usize], &[_]) = ($perm, $value);
+        let (sl, pl, vl) = (slice.len(), perm.len(), value.len());
+
+        $crate::__assert_len_eq!(pl, sl, "line {}: permutation length ({}) is invalid, expected: {}", LINE, pl, sl);
+        $crate::__assert_len_eq!(vl, sl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+        if let Some(index) = perm.iter().position(|&index| index >= sl) {
+            panic!("line {}: permutation index perm[{}] ({}) is out of bounds for length {}", LINE, index, perm[index], sl);
+        }
+
+        if cfg!(debug_assertions) {
+            for (i, index) in perm.iter().enumerate() {
+                if let Some(j) = perm[i + 1..].iter().position(|other| other == index) {
+                    panic!("line {}: permutation index perm[{}] ({}) is a duplicate of perm[{}]", LINE, i + 1 + j, index, i);
+                }
+            }
+        }
+
+        for (&index, value) in perm.iter().zip(value) {
+            slice[index] = $crate::__set_slice_internals!(@element $option value);
+        }
+    }};
+    ($($ln:tt),* => deplane $slice:expr, $stride:expr, $offset:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], stride: usize, offset: usize, value: &[T]) {
+            assert!(offset < stride, "line {}: offset ({}) is invalid, expected less than the stride: {}", LINE, offset, stride);
+
+            let (sl, pl) = (slice.len(), value.len() / stride);
+
+            $crate::__assert_len_eq!(sl, pl, "line {}: slice length ({}) is invalid, expected: {} (the plane length)", LINE, sl, pl);
+
+            for (item, value) in slice.iter_mut().zip(value.iter().skip(offset).step_by(stride)) {
+                *item = *value;
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $stride, $offset, $value);
+    }};
+    ($($ln:tt),* => replane $option:ident $slice:expr, $stride:expr, $offset:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let (stride, offset, value): (usize, usize, &[_]) = ($stride, $offset, $value);
+
+        assert!(offset < stride, "line {}: offset ({}) is invalid, expected less than the stride: {}", LINE, offset, stride);
+
+        let (sl, pl) = (slice.len(), value.len() * stride);
+
+        $crate::__assert_len_eq!(sl, pl, "line {}: slice length ({}) is invalid, expected: {} (the packed length)", LINE, sl, pl);
+
+        for (item, value) in slice.iter_mut().skip(offset).step_by(stride).zip(value) {
+            *item = $crate::__set_slice_internals!(@element $option value);
+        }
+    }};
+    ($($ln:tt),* => repeat_n copy $slice:expr, $count:literal, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        const COUNT: usize = $count;
+
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, COUNT * vl, "line {}: slice length ({}) is invalid, expected: {} ({} copies)", LINE, sl, COUNT * vl, COUNT);
+
+            for i in 0..COUNT {
+                slice[i * vl..][..vl].copy_from_slice(value);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $value);
+    }};
+    ($($ln:tt),* => step move $slice:expr, $step:expr, [$($value:expr),+]) => {{
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value = [$($value),+];
+        let step = $crate::__set_slice_internals!(@step_slots $($ln),* => slice, $step, value.len());
+
+        for (item, value) in slice.iter_mut().step_by(step).zip(IntoIterator::into_iter(value)) {
+            *item = value;
+        }
+    }};
+    ($($ln:tt),* => step $option:ident $slice:expr, $step:expr, $value:expr) => {{
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = $value;
+        let step = $crate::__set_slice_internals!(@step_slots $($ln),* => slice, $step, value.len());
+
+        for (item, value) in slice.iter_mut().step_by(step).zip(value) {
+            *item = $crate::__set_slice_internals!(@element $option value);
+        }
+    }};
+    ($($ln:tt),* => copy_within $slice:expr, $range:expr, $source:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let sl = slice.len();
+        let (start, end, open) = $crate::__range_bounds(&$range, sl);
+        let (source_start, source_end, _) = $crate::__range_bounds(&$source, sl);
+
+        assert!(
+            source_start <= source_end && source_end <= sl,
+            "line {}: source range {}..{} is out of bounds for length {}", LINE, source_start, source_end, sl
+        );
+        assert!(start <= end && end <= sl, "line {}: range {}..{} is out of bounds for length {}", LINE, start, end, sl);
+
+        let (vl, room) = (source_end - source_start, end - start);
+
+        if open {
+            assert!(vl <= room, "line {}: value length ({}) is invalid, expected at most: {}", LINE, vl, room);
+        } else {
+            $crate::__assert_len_eq!(room, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, room);
+        }
+
+        slice.copy_within(source_start..source_end, start);
+    }};
+    ($($ln:tt),* => init $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [$crate::__MaybeUninit<_>] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = $value;
+        let (sl, vl) = (slice.len(), value.len());
+
+        // this guards `__assume_init_mut` below, so it stays on with `debug_checks`
+        assert_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+        for (item, value) in slice.iter_mut().zip(value) {
+            item.write($crate::__set_slice_internals!(@element $option value));
+        }
+
+        // every slot was written above
+        unsafe { $crate::__assume_init_mut(slice) }
+    }};
+    ($($ln:tt),* => repeat $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = $value;
+        let (sl, vl) = (slice.len(), value.len());
+        let extra = if vl == 0 { sl } else { sl % vl };
+
+        $crate::__assert_len_eq!(extra, 0, "line {}: slice length ({}) is invalid, expected a multiple of the pattern length ({})", LINE, sl, vl);
+
+        for chunk in slice.chunks_mut(vl.max(1)) {
+            $crate::__set_slice_internals!($option chunk, value);
+        }
+    }};
+    ($($ln:tt),* => rotated_left $slice:expr, $amount:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy>(slice: &mut [T], amount: usize, value: &[T]) {
+            let (sl, vl) = (slice.len(), value.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            if vl != 0 {
+                let mid = vl - amount % vl;
+
+                slice[..mid].copy_from_slice(&value[vl - mid..]);
+                slice[mid..].copy_from_slice(&value[..vl - mid]);
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $amount, $value);
+    }};
+    ($($ln:tt),* => lut $slice:expr, $table:expr, $clamp:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+
+        #[inline(always)]
+        fn set<T: Copy, I: Copy + Into<usize>>(slice: &mut [T], table: &[T], clamp: bool, value: &[I]) {
+            let (sl, vl, tl) = (slice.len(), value.len(), table.len());
+
+            $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+            if clamp {
+                assert!(tl != 0 || vl == 0, "line {}: the table is empty, so there is nothing to clamp to", LINE);
+            }
+
+            for (i, (item, &index)) in slice.iter_mut().zip(value.iter()).enumerate() {
+                let index: usize = index.into();
+
+                *item = match table.get(index) {
+                    Some(&entry) => entry,
+                    None if clamp => table[tl - 1],
+                    None => panic!("line {}: index {} at {} is out of bounds for a table of length {}", LINE, index, i, tl),
+                };
+            }
+        }
+
+        set($crate::__as_mut_slice!($slice), $table, $clamp, $value);
+    }};
+    ($($ln:tt),* => reverse $slice:expr) => {{
+        let slice = $crate::__as_mut_slice!($slice);
+
+        slice.reverse();
+    }};
+    ($($ln:tt),* => reverse $option:ident $slice:expr, $value:expr) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+        let value: &[_] = $value;
+        let (sl, vl) = (slice.len(), value.len());
+
+        $crate::__assert_len_eq!(sl, vl, "line {}: value length ({}) is invalid, expected: {}", LINE, vl, sl);
+
+        for (item, value) in slice.iter_mut().zip(value.iter().rev()) {
+            *item = $crate::__set_slice_internals!(@element $option value);
+        }
+    }};
+}
+
+/// a macro for setting parts of slices, see crate level docs for more info 
+#[macro_export]
+macro_rules! set_slice {
+    // named branches
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident = move $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => move $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => $slice, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => move $slice, [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident[$($range:tt)*] = move $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => move $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident[$($range:tt)*] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[name = $name:expr] $slice:ident[$($range:tt)*] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!(@named ($name) $($ln),* => move $crate::__as_mut_slice!($slice)[$($range)*], [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => #[$($attr:tt)*] $($rest:tt)*) => {
+        compile_error!("the only instruction attribute is #[name = \"...\"], and it must be followed by a list, move or copy like instruction");
+    };
+
+    // cursor branches
+    (@$($ln:tt),* => @cursor $slice:ident: $($body:tt)*) => {{
+        let mut cursor = 0usize;
+        set_slice!(@cursor ($($ln),*) $slice, cursor, $($body)*)
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, write $endian:ident $int:ident $value:expr; $($rest:tt)*) => {{
+        set_slice!(@cursor ($($ln),*) $slice, $cursor, write $endian $int $value);
+        set_slice!(@cursor ($($ln,)* 0) $slice, $cursor, $($rest)*)
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, write le $int:ident $value:expr) => {{
+        let bytes = $int::to_le_bytes(*$value);
+        set_slice!(@cursor ($($ln),*) $slice, $cursor, write &bytes)
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, write be $int:ident $value:expr) => {{
+        let bytes = $int::to_be_bytes(*$value);
+        set_slice!(@cursor ($($ln),*) $slice, $cursor, write &bytes)
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, write $value:expr; $($rest:tt)*) => {{
+        __set_slice_internals!($($ln),* => cursor write $slice, $cursor, $value);
+        set_slice!(@cursor ($($ln,)* 0) $slice, $cursor, $($rest)*)
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, write $value:expr) => {{
+        __set_slice_internals!($($ln),* => cursor write $slice, $cursor, $value);
+        $cursor
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident,) => {{
+        let _ = $cursor;
+    }};
+    (@cursor ($($ln:tt),*) $slice:ident, $cursor:ident, $($rest:tt)+) => {
+        compile_error!("a cursor only supports write statements, like `write &value;` or `write le u32 &value;`")
+    };
+
+    // 2d branches
+    (@$($ln:tt),* => $slice:ident @ ($width:expr) rect($x:expr, $y:expr, $w:expr, $h:expr) = fill $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => rect fill $slice, $width, ($x, $y, $w, $h), $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident @ ($width:expr) rect($x:expr, $y:expr, $w:expr, $h:expr) = copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => rect copy $slice, $width, ($x, $y, $w, $h), $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $grid:ident col($col:expr) = copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => col copy $grid, $col, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $grid:ident row($row:expr) $($rest:tt)*) => {
+        set_slice!(@$($ln),* => $grid[$row] $($rest)*);
+    };
+    (@$($ln:tt),* => $grid:ident[$($rows:tt)*][$($cols:tt)*] = copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => grid copy &mut $crate::__as_mut_slice!($grid)[$($rows)*], $($cols)*, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $grid:ident[$($rows:tt)*][$($cols:tt)*] = clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => grid clone &mut $crate::__as_mut_slice!($grid)[$($rows)*], $($cols)*, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // 3d branches
+    (@$($ln:tt),* => $slice:ident @ ($d1:expr, $d2:expr) [$x:expr, $y:expr, ..] $($rest:tt)*) => {{
+        let line = __set_slice_internals!(@line_range $($ln),* => $slice, ($d1, $d2), $x, $y);
+        set_slice!(@$($ln),* => $slice[line] $($rest)*);
+    }};
+
+    // span branches
+    (@$($ln:tt),* => $slice:ident at($offset:expr, $len:expr) $($rest:tt)*) => {{
+        let span = __set_slice_internals!(@span_range $($ln),* => $slice, $offset, $len);
+        set_slice!(@$($ln),* => $slice[span] $($rest)*);
+    }};
+
+    // fence branches
+    (@$($ln:tt),* => fence(Relaxed); $($rest:tt)*) => {
+        compile_error!("A fence can not be Relaxed, use one of Acquire, Release, AcqRel or SeqCst");
+    };
+    (@$($ln:tt),* => atomic fence(Relaxed); $($rest:tt)*) => {
+        compile_error!("A fence can not be Relaxed, use one of Acquire, Release, AcqRel or SeqCst");
+    };
+    (@$($ln:tt),* => fence($ordering:ident); $($rest:tt)*) => {
+        $crate::__compiler_fence($crate::__Ordering::$ordering);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => atomic fence($ordering:ident); $($rest:tt)*) => {
+        $crate::__fence($crate::__Ordering::$ordering);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // split branches
+    (@$($ln:tt),* => $slice:ident => (head, rest) = ($head:expr, $option:ident $value:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => split split_first_mut $slice, $head, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident => (rest, last) = ($option:ident $value:expr, $last:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => split split_last_mut $slice, $last, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] => (head, rest) = ($head:expr, $option:ident $value:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => split split_first_mut $crate::__as_mut_slice!($slice)[$($range)*], $head, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] => (rest, last) = ($option:ident $value:expr, $last:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => split split_last_mut $crate::__as_mut_slice!($slice)[$($range)*], $last, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // statistics branches
+    (@$($ln:tt),* => with_stats $slice:ident = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => with_stats copy $slice, $value)
+    };
+    (@$($ln:tt),* => with_stats $slice:ident[$($range:tt)*] = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => with_stats copy $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+    (@$($ln:tt),* => diff_log $slice:ident = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => diff_log copy $slice, $value)
+    };
+    (@$($ln:tt),* => diff_log $slice:ident[$($range:tt)*] = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => diff_log copy $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+    (@$($ln:tt),* => with_count $slice:ident[!$mask:expr] = fill $value:expr) => {
+        __set_slice_internals!($($ln),* => masked fill true, $slice, $mask, $value)
+    };
+    (@$($ln:tt),* => with_count $slice:ident[!$mask:expr] = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => masked copy true, $slice, $mask, $value)
+    };
+    (@$($ln:tt),* => with_count $slice:ident[$mask:expr] = fill $value:expr) => {
+        __set_slice_internals!($($ln),* => masked fill false, $slice, $mask, $value)
+    };
+    (@$($ln:tt),* => with_count $slice:ident[$mask:expr] = copy $value:expr) => {
+        __set_slice_internals!($($ln),* => masked copy false, $slice, $mask, $value)
+    };
+
+    // mask branches
+    (@$($ln:tt),* => $slice:ident[!$mask:expr] = copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => masked copy true, $slice, $mask, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // cover branches
+    (@$($ln:tt),* => cover: $slice:ident => { $($body:tt)* }; $($rest:tt)*) => {
+        set_slice!(@$($ln),* => cover: $slice => { $($body)* } $($rest)*);
+    };
+    (@$($ln:tt),* => cover: $slice:ident => { $($body:tt)* } $($rest:tt)*) => {
+        set_slice!(@cover ($($ln),*) $slice, 0, [], [] $($body)*);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@cover ($($ln:tt),*) $slice:ident, $prev:literal, [$($checks:tt)*], [$($out:tt)*] [$start:literal .. $end:literal] $($body:tt)*) => {
+        set_slice!(@cover_statement ($($ln),*) $slice, $end, [$($checks)* ($prev, $start, $end)], [$($out)* $slice[$start..$end]] $($body)*);
+    };
+    (@cover ($($ln:tt),*) $slice:ident, $end:literal, [$(($prev:literal, $start:literal, $stop:literal))*], [$($out:tt)*]) => {{
+        const LINE: usize = $crate::count!($($ln)*);
+        const _: () = {
+            $(
+                assert!($start <= $prev, concat!(
+                    "cover: there is a gap ", stringify!($prev), "..", stringify!($start),
+                    " before range ", stringify!($start), "..", stringify!($stop)
+                ));
+                assert!($start >= $prev, concat!(
+                    "cover: range ", stringify!($start), "..", stringify!($stop),
+                    " overlaps the previous range, which ends at ", stringify!($prev)
+                ));
+                assert!($start <= $stop, concat!(
+                    "cover: range ", stringify!($start), "..", stringify!($stop), " is decreasing"
+                ));
+            )*
+        };
+        let sl = $slice.len();
+
+        assert_eq!(sl, $end, "line {}: slice length ({}) is invalid, expected the ranges to end at: {}", LINE, sl, $end);
+        set_slice!(@$($ln),* => $($out)*);
+    }};
+    (@cover ($($ln:tt),*) $slice:ident, $prev:literal, [$($checks:tt)*], [$($out:tt)*] $($body:tt)+) => {
+        compile_error!("cover: each statement must start with a literal range, like [0..4]");
+    };
+    (@cover_statement ($($ln:tt),*) $slice:ident, $prev:literal, [$($checks:tt)*], [$($out:tt)*] ; $($body:tt)*) => {
+        set_slice!(@cover ($($ln),*) $slice, $prev, [$($checks)*], [$($out)* ;] $($body)*);
+    };
+    (@cover_statement ($($ln:tt),*) $slice:ident, $prev:literal, [$($checks:tt)*], [$($out:tt)*] $next:tt $($body:tt)*) => {
+        set_slice!(@cover_statement ($($ln),*) $slice, $prev, [$($checks)*], [$($out)* $next] $($body)*);
+    };
+
+    // run-length branches
+    (@$($ln:tt),* => $out:ident = runs & $source:ident[$($range:tt)*] $($rest:tt)*) => {
+        set_slice!(@runs ($($ln),*) $out, ($source[$($range)*]), [] $($rest)*);
+    };
+    (@$($ln:tt),* => $out:ident = runs & $source:ident $($rest:tt)*) => {
+        set_slice!(@runs ($($ln),*) $out, ($source), [] $($rest)*);
+    };
+    (@runs ($($ln:tt),*) $out:ident, ($($source:tt)*), [$($predicate:tt)*] map $summary:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => runs $out, $($source)*, $($predicate)*, $summary);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@runs ($($ln:tt),*) $out:ident, ($($source:tt)*), [$($predicate:tt)*] $next:tt $($rest:tt)*) => {
+        set_slice!(@runs ($($ln),*) $out, ($($source)*), [$($predicate)* $next] $($rest)*);
+    };
+
+    // swap branches
+    (@$($ln:tt),* => $slice:ident <=> $($rest:tt)*) => {
+        set_slice!(@swap ($($ln),*) ($crate::__as_mut_slice!($slice)[..]) $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] <=> $($rest:tt)*) => {
+        set_slice!(@swap ($($ln),*) ($crate::__as_mut_slice!($slice)[$($range)*]) $($rest)*);
+    };
+    (@swap ($($ln:tt),*) ($($slice:tt)*) $other:ident[$($range:tt)*]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => swap $($slice)*, $other[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@swap ($($ln:tt),*) ($($slice:tt)*) $other:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => swap $($slice)*, $other[..]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // field branches
+    (@$($ln:tt),* => $slice:ident.$field:tt = copy_field $($rest:tt)*) => {
+        set_slice!(@field ($($ln),*) ($slice), $field, $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*].$field:tt = copy_field $($rest:tt)*) => {
+        set_slice!(@field ($($ln),*) ($crate::__as_mut_slice!($slice)[$($range)*]), $field, $($rest)*);
+    };
+    (@field ($($ln:tt),*) ($($slice:tt)*), $field:tt, & $source:ident[$($range:tt)*].$source_field:tt; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_field $($slice)*, $field, $source[$($range)*], $source_field);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@field ($($ln:tt),*) ($($slice:tt)*), $field:tt, & $source:ident.$source_field:tt; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_field $($slice)*, $field, $source, $source_field);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@field ($($ln:tt),*) ($($slice:tt)*), $field:tt, $($rest:tt)*) => {
+        compile_error!("copy_field takes a reference to a field of a slice, like `copy_field &source.1;`");
+    };
+
+    // expression target branches
+    (@$($ln:tt),* => $head:ident . $($rest:tt)*) => {
+        set_slice!(@target ($($ln),*) place [$head] . $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] . $method:ident ($($args:tt)*) $($rest:tt)*) => {
+        set_slice!(@target ($($ln),*) call [$($target)* . $method ($($args)*)] $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] . $field:tt = copy_field $($rest:tt)*) => {
+        set_slice!(@target_end ($($ln),*) $kind [$($target)*] . $field = copy_field $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] . $field:tt $($rest:tt)*) => {
+        set_slice!(@target ($($ln),*) place [$($target)* . $field] $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] [$($index:tt)*] . $field:tt = copy_field $($rest:tt)*) => {
+        set_slice!(@target_end ($($ln),*) $kind [$($target)*] [$($index)*] . $field = copy_field $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] [$($index:tt)*] . $($rest:tt)*) => {
+        set_slice!(@target ($($ln),*) place [$($target)* [$($index)*]] . $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] [$($index:tt)*] [$($next:tt)*] $($rest:tt)*) => {
+        set_slice!(@target ($($ln),*) place [$($target)* [$($index)*]] [$($next)*] $($rest)*)
+    };
+    (@target ($($ln:tt),*) $kind:ident [$($target:tt)*] $($rest:tt)*) => {
+        set_slice!(@target_end ($($ln),*) $kind [$($target)*] $($rest)*)
+    };
+    // a method call returns a reference, which is reborrowed so the target is not a reference to a temporary
+    (@target_end ($($ln:tt),*) call [$($target:tt)*] $($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut target = &mut *$($target)*;
+        set_slice! { @$($ln),* => target $($rest)* }
+    }};
+    (@target_end ($($ln:tt),*) place [$($target:tt)*] $($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut target = &mut $($target)*;
+        set_slice! { @$($ln),* => target $($rest)* }
+    }};
+
+    // step branches
+    (@$($ln:tt),* => $slice:ident[$range:expr; step $step:expr] = copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => step copy $crate::__as_mut_slice!($slice)[$range], $step, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$range:expr; step $step:expr] = clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => step clone $crate::__as_mut_slice!($slice)[$range], $step, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$range:expr; step $step:expr] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => step move $crate::__as_mut_slice!($slice)[$range], $step, [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$range:expr; step $step:expr] $($rest:tt)*) => {
+        compile_error!("a stepped range only supports a list, `copy REFERENCE;` or `clone REFERENCE;`");
+    };
+
+    // multiple target branches
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*], $($rest:tt)*) => {
+        set_slice!(@targets ($($ln),*) [($slice [$($range)*])] $($rest)*);
+    };
+    (@targets $lns:tt [$($targets:tt)*] $slice:ident[$($range:tt)*], $($rest:tt)*) => {
+        set_slice!(@targets $lns [$($targets)* ($slice [$($range)*])] $($rest)*);
+    };
+    (@targets $lns:tt [$(($target:ident [$($range:tt)*]))*] $slice:ident[$($last:tt)*] = $option:ident $value:expr; $($rest:tt)*) => {{
+        let value = $value;
+        $(set_slice!(@target_each $lns $target [$($range)*], $option value);)*
+        set_slice!(@target_each $lns $slice [$($last)*], $option value => $($rest)*);
+    }};
+    (@targets $lns:tt [$($targets:tt)*] $($rest:tt)*) => {
+        compile_error!("multiple targets only support `SLICE[RANGE], SLICE[RANGE], ... = OPTION VALUE;`");
+    };
+    (@target_each ($($ln:tt),*) $slice:ident [$($range:tt)*], $option:ident $value:ident) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $value);
+    };
+    (@target_each ($($ln:tt),*) $slice:ident [$($range:tt)*], $option:ident $value:ident => $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // no range branches
+    (@$($ln:tt),* => unsafe $slice:ident: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ref $slice, $size, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => unsafe $slice:ident = ref $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ref $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = reverse copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = reverse clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse clone $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = reverse; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = reverse) => {{
+        __set_slice_internals!($($ln),* => reverse $slice);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = map $function:expr) => {{
+        __set_slice_internals!($($ln),* => $slice, map $function);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $slice, fill $value);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = clone_fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $slice, clone_fill $value);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = fill_copy $value:expr) => {{
+        __set_slice_internals!($($ln),* => $slice, fill_copy $value);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = copy_fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $slice, copy_fill $value);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+    (@$($ln:tt),* => $slice:ident = default) => {{
+        __set_slice_internals!($($ln),* => default $slice);
+        &mut $crate::__as_mut_slice!($slice)[..]
+    }};
+
+    (@$($ln:tt),* => $slice:ident = clamp_copy $value:expr, $low:expr, $high:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => clamp_copy $slice, $value, $low, $high);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = merge $left:expr, $right:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => merge $slice, $left, $right);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = permute($perm:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => permute $option $slice, $perm, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = scatter_permute($perm:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => scatter_permute $option $slice, $perm, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = deplane($stride:expr, $offset:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => deplane $slice, $stride, $offset, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = replane($stride:expr, $offset:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => replane $option $slice, $stride, $offset, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = rotated_left($amount:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => rotated_left $slice, $amount, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = lut($table:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => lut $slice, $table, false, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = lut($table:expr, clamp) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => lut $slice, $table, true, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $slice, copy <str>::as_bytes($value));
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = from_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes ne $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = from_le_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes le $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = from_be_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes be $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = extend copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => extend copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = extend clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => extend clone $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = init $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => init $option $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = init $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => init $option $slice, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident = saturating $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => saturating $option $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = saturating $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => saturating $option $slice, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident = copy_within $source:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_within $slice, .., $source);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = repeat clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat clone $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = repeat $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = repeat_n($count:literal) copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat_n copy $slice, $count, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = pack $endian:ident $int:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => pack $endian $int $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = unpack $endian:ident $int:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => unpack $endian $int $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = truncate $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => truncate $option $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = truncate $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => truncate $option $slice, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident = saturating_add copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => saturating_add copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident =$overflow:ident $op:tt copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => compound $overflow $op copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = volatile copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => volatile copy $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = dedup_into $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => dedup_into $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = dedup_into $value:expr) => {
+        __set_slice_internals!($($ln),* => dedup_into $slice, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident = copy_until($sentinel:expr, truncate) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_until $slice, $sentinel, true, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = copy_until($sentinel:expr, truncate) $value:expr) => {
+        __set_slice_internals!($($ln),* => copy_until $slice, $sentinel, true, $value)
+    };
+    (@$($ln:tt),* => $slice:ident = copy_until($sentinel:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_until $slice, $sentinel, false, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = copy_until($sentinel:expr) $value:expr) => {
+        __set_slice_internals!($($ln),* => copy_until $slice, $sentinel, false, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident = checksum $algorithm:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checksum $algorithm $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = variant_table($($fill:tt)*) { $($variant:path => $value:expr),* $(,)* }; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => variant_table $slice, ($($fill)*), { $($variant => $value),* });
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = some_all $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => some_all $option $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = none_all; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => none_all $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = default; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => default $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = default $value:expr $(; $($rest:tt)*)?) => {
+        compile_error!("default takes no value: write `default;` to reset to `T::default()`, or `fill VALUE;` to fill with a value");
+    };
+
+    (@$($ln:tt),* => $slice:ident = ramp($start:expr, $end:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $slice, $start, $end);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = fade_in; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $slice, 0.0, 1.0);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = fade_out; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $slice, 1.0, 0.0);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = window($window:ident); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => window $window $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = normalize checked $norm:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => normalize true, $norm $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = normalize $norm:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => normalize false, $norm $slice);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = try_fill_with $factory:expr) => {
+        __set_slice_internals!($($ln),* => try_fill_with $slice, $factory)
+    };
+
+    (@$($ln:tt),* => $slice:ident = from_deque clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_deque clone $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = fma $a:expr, $b:expr, $scalar:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => fma $slice, $a, $b, $scalar);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = fma $a:expr, $b:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => fma $slice, $a, $b);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = move $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => move $slice, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $option:ident &$source:ident[min = $len:expr]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $slice, $option $source[min = $len]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $option:ident &$source:ident[max = $len:expr]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $slice, $option $source[max = $len]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $option:ident $source:ident[$($range:tt)*]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $slice, $option $source[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $slice, $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $min:literal $op:tt $max:literal: $($value:literal),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checked const $slice, $min $op $max, $($value),+);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $min:literal $op:tt $max:literal: $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checked $slice, $min $op $max, $($value),+);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = & $value:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => single_ref $slice, &$value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident = & $value:ident[$($index:tt)*]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => single_ref $slice, &$value[$($index)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => move $slice, [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // with range branches
+    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*]: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ref $crate::__as_mut_slice!($slice)[$($range)*], $size, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*] = ref $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ref $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$index:literal] = ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Copying arbitrary references in unsafe");
+    };
+
+    (@$($ln:tt),* => $slice:ident[$index:literal] = move $value:expr; $($rest:tt)*) => {
+        $crate::__as_mut_slice!($slice)[$index] = $value;
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = reverse copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse copy $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = reverse clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse clone $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = reverse; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => reverse $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = reverse) => {{
+        __set_slice_internals!($($ln),* => reverse $crate::__as_mut_slice!($slice)[$($range)*]);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = map $function:expr) => {{
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], map $function);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], fill $value);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = clone_fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], clone_fill $value);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fill_copy $value:expr) => {{
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], fill_copy $value);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_fill $value:expr) => {{
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], copy_fill $value);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = default) => {{
+        __set_slice_internals!($($ln),* => default $crate::__as_mut_slice!($slice)[$($range)*]);
+        &mut $crate::__as_mut_slice!($slice)[$($range)*]
+    }};
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = clamp_copy $value:expr, $low:expr, $high:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => clamp_copy $crate::__as_mut_slice!($slice)[$($range)*], $value, $low, $high);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = merge $left:expr, $right:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => merge $crate::__as_mut_slice!($slice)[$($range)*], $left, $right);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = permute($perm:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => permute $option $crate::__as_mut_slice!($slice)[$($range)*], $perm, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = scatter_permute($perm:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => scatter_permute $option $crate::__as_mut_slice!($slice)[$($range)*], $perm, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = deplane($stride:expr, $offset:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => deplane $crate::__as_mut_slice!($slice)[$($range)*], $stride, $offset, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = replane($stride:expr, $offset:expr) $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => replane $option $crate::__as_mut_slice!($slice)[$($range)*], $stride, $offset, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = rotated_left($amount:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => rotated_left $crate::__as_mut_slice!($slice)[$($range)*], $amount, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = lut($table:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => lut $crate::__as_mut_slice!($slice)[$($range)*], $table, false, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = lut($table:expr, clamp) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => lut $crate::__as_mut_slice!($slice)[$($range)*], $table, true, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], copy <str>::as_bytes($value));
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = from_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes ne $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = from_le_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes le $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = from_be_bytes $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_bytes be $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = init $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => init $option $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = init $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => init $option $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = saturating $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => saturating $option $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = saturating $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => saturating $option $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_within $source:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_within $slice, ($($range)*), $source);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = repeat clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat clone $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = repeat $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat copy $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = repeat_n($count:literal) copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => repeat_n copy $crate::__as_mut_slice!($slice)[$($range)*], $count, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = pack $endian:ident $int:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => pack $endian $int $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = unpack $endian:ident $int:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => unpack $endian $int $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = truncate $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => truncate $option $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = truncate $option:ident $value:expr) => {
+        __set_slice_internals!($($ln),* => truncate $option $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = saturating_add copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => saturating_add copy $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] =$overflow:ident $op:tt copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => compound $overflow $op copy $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = volatile copy $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => volatile copy $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = dedup_into $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => dedup_into $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = dedup_into $value:expr) => {
+        __set_slice_internals!($($ln),* => dedup_into $crate::__as_mut_slice!($slice)[$($range)*], $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[..$len:expr] = add_unique $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => add_unique $slice, $len, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[..$len:expr] = add_unique $value:expr) => {
+        __set_slice_internals!($($ln),* => add_unique $slice, $len, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_until($sentinel:expr, truncate) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_until $crate::__as_mut_slice!($slice)[$($range)*], $sentinel, true, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_until($sentinel:expr, truncate) $value:expr) => {
+        __set_slice_internals!($($ln),* => copy_until $crate::__as_mut_slice!($slice)[$($range)*], $sentinel, true, $value)
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_until($sentinel:expr) $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => copy_until $crate::__as_mut_slice!($slice)[$($range)*], $sentinel, false, $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_until($sentinel:expr) $value:expr) => {
+        __set_slice_internals!($($ln),* => copy_until $crate::__as_mut_slice!($slice)[$($range)*], $sentinel, false, $value)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = checksum $algorithm:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checksum $algorithm $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = variant_table($($fill:tt)*) { $($variant:path => $value:expr),* $(,)* }; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => variant_table $crate::__as_mut_slice!($slice)[$($range)*], ($($fill)*), { $($variant => $value),* });
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = some_all $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => some_all $option $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = none_all; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => none_all $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = default; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => default $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = default $value:expr $(; $($rest:tt)*)?) => {
+        compile_error!("default takes no value: write `default;` to reset to `T::default()`, or `fill VALUE;` to fill with a value");
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = ramp($start:expr, $end:expr); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $crate::__as_mut_slice!($slice)[$($range)*], $start, $end);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fade_in; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $crate::__as_mut_slice!($slice)[$($range)*], 0.0, 1.0);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fade_out; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => ramp $crate::__as_mut_slice!($slice)[$($range)*], 1.0, 0.0);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = window($window:ident); $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => window $window $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = normalize checked $norm:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => normalize true, $norm $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = normalize $norm:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => normalize false, $norm $crate::__as_mut_slice!($slice)[$($range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = try_fill_with $factory:expr) => {
+        __set_slice_internals!($($ln),* => try_fill_with $crate::__as_mut_slice!($slice)[$($range)*], $factory)
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = from_deque clone $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => from_deque clone $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fma $a:expr, $b:expr, $scalar:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => fma $crate::__as_mut_slice!($slice)[$($range)*], $a, $b, $scalar);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fma $a:expr, $b:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => fma $crate::__as_mut_slice!($slice)[$($range)*], $a, $b);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = move $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => move $crate::__as_mut_slice!($slice)[$($range)*], $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident &$source:ident[min = $len:expr]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $source[min = $len]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident &$source:ident[max = $len:expr]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $source[max = $len]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident $source:ident[$($source_range:tt)*]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $source[$($source_range)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal .. $end:literal] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_range $slice, ($start .. $end), ($start, $end), $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal ..= $end:literal] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_range $slice, ($start ..= $end), ($start, $end + 1), $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal ..] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_range $slice, ($start ..), ($start, usize::MAX), $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[.. $end:literal] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_range $slice, (.. $end), (0, $end), $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[..= $end:literal] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_range $slice, (..= $end), (0, $end + 1), $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal .. $end:literal] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_list $slice, ($start .. $end), ($start, $end), [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal ..= $end:literal] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_list $slice, ($start ..= $end), ($start, $end + 1), [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$start:literal ..] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_list $slice, ($start ..), ($start, usize::MAX), [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[.. $end:literal] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_list $slice, (.. $end), (0, $end), [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[..= $end:literal] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => const_list $slice, (..= $end), (0, $end + 1), [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident $value:expr; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => $crate::__as_mut_slice!($slice)[$($range)*], $option $value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$index:literal] = $value:expr; $($rest:tt)*) => {
+        $crate::__as_mut_slice!($slice)[$index] = $value;
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $min:literal $op:tt $max:literal: $($value:literal),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checked const $crate::__as_mut_slice!($slice)[$($range)*], $min $op $max, $($value),+);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $min:literal $op:tt $max:literal: $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => checked $crate::__as_mut_slice!($slice)[$($range)*], $min $op $max, $($value),+);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = & $value:ident; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => single_ref $crate::__as_mut_slice!($slice)[$($range)*], &$value);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = & $value:ident[$($index:tt)*]; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => single_ref $crate::__as_mut_slice!($slice)[$($range)*], &$value[$($index)*]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $($value:expr),+; $($rest:tt)*) => {
+        __set_slice_internals!($($ln),* => move $crate::__as_mut_slice!($slice)[$($range)*], [$($value),+]);
+        set_slice!(@$($ln,)* 0 => $($rest)*);
+    };
+
+    // remainder branches
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident $value:expr => rest) => {{
+        let (target, rest) = $crate::__split_remainder($crate::__as_mut_slice!($slice), $($range)*);
+        set_slice!(@$($ln),* => target[..] = $option $value;);
+        rest
+    }};
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $($value:expr),+ => rest) => {{
+        let (target, rest) = $crate::__split_remainder($crate::__as_mut_slice!($slice), $($range)*);
+        set_slice!(@$($ln),* => target[..] = $($value),+;);
+        rest
+    }};
+
+    // errors and terminals
+    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*]: ($size:expr) = $value:expr; $($rest:tt)*) => {
+        compile_error!("Moving values into the slice is safe");
+    };
+    (@$($ln:tt),* => unsafe $slice:ident: ($size:expr) = $value:expr; $($rest:tt)*) => {
+        compile_error!("Moving values into the slice is safe");
+    };
+    
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*]: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Copying arbitrary references in unsafe");
+    };
+    (@$($ln:tt),* => $slice:ident: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Copying arbitrary references in unsafe");
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Copying arbitrary references in unsafe");
+    };
+    (@$($ln:tt),* => $slice:ident= ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Copying arbitrary references in unsafe");
+    };
+    
+    (@$($ln:tt),* => $slice:ident: $($rest:tt)*) => {
+        compile_error!("Invalid size: size must be an expression surrouned by parentheses");
+    };
+
+    (@$($ln:tt),* => $slice:ident = ref $value:expr; $($rest:tt)*) => {
+        compile_error!("Option is missing: value should be of the form: \"{copy, clone} ref value\"")
+    };
+
+    (@$($ln:tt),* => $slice:ident = fill ref $($rest:tt)*) => {
+        compile_error!("fill takes a value, not a reference: write `fill VALUE;` to fill with a value, or `clone REFERENCE;` to clone a slice");
+    };
+    (@$($ln:tt),* => $slice:ident = clone_fill ref $($rest:tt)*) => {
+        compile_error!("clone_fill takes a value, not a reference: write `clone_fill VALUE;` to fill with a value, or `clone REFERENCE;` to clone a slice");
+    };
+    (@$($ln:tt),* => $slice:ident = fill_copy ref $($rest:tt)*) => {
+        compile_error!("fill_copy takes a value, not a reference: write `fill_copy VALUE;` to fill with a value, or `copy REFERENCE;` to copy a slice");
+    };
+    (@$($ln:tt),* => $slice:ident = copy_fill ref $($rest:tt)*) => {
+        compile_error!("copy_fill takes a value, not a reference: write `copy_fill VALUE;` to fill with a value, or `copy REFERENCE;` to copy a slice");
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fill ref $($rest:tt)*) => {
+        compile_error!("fill takes a value, not a reference: write `fill VALUE;` to fill with a value, or `clone REFERENCE;` to clone a slice");
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = clone_fill ref $($rest:tt)*) => {
+        compile_error!("clone_fill takes a value, not a reference: write `clone_fill VALUE;` to fill with a value, or `clone REFERENCE;` to clone a slice");
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = fill_copy ref $($rest:tt)*) => {
+        compile_error!("fill_copy takes a value, not a reference: write `fill_copy VALUE;` to fill with a value, or `copy REFERENCE;` to copy a slice");
+    };
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = copy_fill ref $($rest:tt)*) => {
+        compile_error!("copy_fill takes a value, not a reference: write `copy_fill VALUE;` to fill with a value, or `copy REFERENCE;` to copy a slice");
+    };
+    (@$($ln:tt),* => $slice:ident = ; $($rest:tt)*) => {
+        compile_error!("There must be a non-zero number of arguments in a list");
+    };
+
+    (@$($ln:tt),* => $slice:ident $($rest:tt)*) => {
+        compile_error!("Punctuation is missing!");
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*]: $($rest:tt)*) => {
+        compile_error!("Invalid size: size must be an expression surrouned by parentheses!");
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = ; $($rest:tt)*) => {
+        compile_error!("There must be a non-zero number of arguments in a list!");
+    };
+
+    (@$($ln:tt),* => $slice:ident[$($range:tt)*] $($rest:tt)*) => {
+        compile_error!("Punctuation is missing!");
+    };
+
+    (@$($ln:tt),* => ) => {};
+    () => {};
+    
+    (@$($ln:tt),* => [$($range:tt)*] $($rest:tt)*) => {
+        compile_error!("Missing identifier, there is a range, but no slice");
+    };
+    (@$($ln:tt),* => $($rest:tt)+) => {
+        compile_error!("Missing rvalue, there seems to be a missing slice to assign to");
+    };
+    ($($rest:tt)+) => {
+        set_slice!(@0 => $($rest)+)
+    };
+}
+
+/// a fallible version of `set_slice!`, see crate level docs for more info
+#[macro_export]
+macro_rules! try_set_slice {
+    (@$label:lifetime, $($ln:tt),* => $slice:ident = extend $value:expr; $($rest:tt)*) => {
+        if let Err(e) = $crate::__try_extend($crate::count!($($ln)*), &mut $slice, $value) {
+            break $label Err($crate::__into_error(e));
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident = move $value:expr; $($rest:tt)*) => {
+        {
+            let val = $value; // capture value
+            let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+            let (sl, vl) = (slice.len(), val.len());
+
+            if let Err(e) = $crate::__check_len($crate::count!($($ln)*), sl, vl) {
+                break $label Err($crate::__into_error(e));
+            }
+
+            __set_slice_internals!($($ln),* => move slice, val);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident = try_fill_with $factory:expr; $($rest:tt)*) => {
+        if let Err(e) = __set_slice_internals!($($ln),* => try_fill_with $slice, $factory) {
+            break $label Err(e);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident = $option:ident $value:expr; $($rest:tt)*) => {
+        {
+            let input: &[_] = $value;
+            let slice: &mut [_] = $crate::__as_mut_slice!($slice);
+
+            if let Err(e) = $crate::__check_len($crate::count!($($ln)*), slice.len(), input.len()) {
+                break $label Err($crate::__into_error(e));
+            }
+
+            __set_slice_internals!($option slice, input);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident = $($value:expr),+; $($rest:tt)*) => {
+        try_set_slice!(@$label, $($ln),* => $slice = move [$($value),+]; $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident[$($range:tt)*] = move $value:expr; $($rest:tt)*) => {
+        {
+            let val = $value; // capture value
+            let slice: &mut [_] = &mut $crate::__as_mut_slice!($slice)[$($range)*];
+            let (sl, vl) = (slice.len(), val.len());
+
+            if let Err(e) = $crate::__check_len($crate::count!($($ln)*), sl, vl) {
+                break $label Err($crate::__into_error(e));
+            }
+
+            __set_slice_internals!($($ln),* => move slice, val);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident[$($range:tt)*] = try_fill_with $factory:expr; $($rest:tt)*) => {
+        if let Err(e) = __set_slice_internals!($($ln),* => try_fill_with $crate::__as_mut_slice!($slice)[$($range)*], $factory) {
+            break $label Err(e);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident $value:expr; $($rest:tt)*) => {
+        {
+            let input: &[_] = $value;
+            let slice: &mut [_] = &mut $crate::__as_mut_slice!($slice)[$($range)*];
+
+            if let Err(e) = $crate::__check_len($crate::count!($($ln)*), slice.len(), input.len()) {
+                break $label Err($crate::__into_error(e));
+            }
+
+            __set_slice_internals!($option slice, input);
+        }
+        try_set_slice!(@$label, $($ln,)* 0 => $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => $slice:ident[$($range:tt)*] = $($value:expr),+; $($rest:tt)*) => {
+        try_set_slice!(@$label, $($ln),* => $slice[$($range)*] = move [$($value),+]; $($rest)*);
+    };
+
+    (@$label:lifetime, $($ln:tt),* => ) => {};
+    (@$label:lifetime, $($ln:tt),* => $($rest:tt)+) => {
+        compile_error!("try_set_slice! only supports the list, move, copy, clone, extend and try_fill_with instructions");
+    };
+
+    // the error type, which is the one of the factories if there is a `try_fill_with`, skipping 4 tokens at a time
+    (@error try_fill_with $($rest:tt)*) => { _ };
+    (@error $a:tt try_fill_with $($rest:tt)*) => { _ };
+    (@error $a:tt $b:tt try_fill_with $($rest:tt)*) => { _ };
+    (@error $a:tt $b:tt $c:tt try_fill_with $($rest:tt)*) => { _ };
+    (@error $a:tt $b:tt $c:tt $d:tt $($rest:tt)*) => { try_set_slice!(@error $($rest)*) };
+    (@error $($rest:tt)*) => { $crate::SetSliceError };
+
+    () => {
+        Ok::<(), $crate::SetSliceError>(())
+    };
+    ($($rest:tt)+) => {
+        'set_slice: {
+            try_set_slice!(@'set_slice, 0 => $($rest)+);
+            Ok::<(), try_set_slice!(@error $($rest)+)>(())
+        }
+    };
+}
+
+/// a macro for building arrays with `set_slice!`, see crate level docs for more info
+#[macro_export]
+macro_rules! set_array {
+    (@statements $array:ident [$($out:tt)*]) => {
+        set_slice! { $($out)* }
+    };
+    (@statements $array:ident [$($out:tt)*] $($rest:tt)+) => {
+        set_array!(@statement $array [$($out)*] [] $($rest)+)
+    };
+    (@statement $array:ident [$($out:tt)*] [$($statement:tt)*] ; $($rest:tt)*) => {
+        set_array!(@statements $array [$($out)* $array $($statement)* ;] $($rest)*)
+    };
+    (@statement $array:ident [$($out:tt)*] [$($statement:tt)*] $next:tt $($rest:tt)*) => {
+        set_array!(@statement $array [$($out)*] [$($statement)* $next] $($rest)*)
+    };
+    (@statement $array:ident [$($out:tt)*] [$($statement:tt)*]) => {
+        set_array!(@statements $array [$($out)* $array $($statement)* ;])
+    };
+
+    ([$type:ty; $len:expr]: $($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut array: [$type; $len] = $crate::__default_array();
+        set_array!(@statements array [] $($body)*);
+        array
+    }};
+    ($($rest:tt)*) => {
+        compile_error!("set_array! must start with the type of the array, like `[u8; 8]:`")
+    };
+}
+
+/// a version of `set_slice!` that puts the source of an instruction in its length mismatch panic,
+/// see crate level docs for more info
+#[macro_export]
+macro_rules! set_slice_debug {
+    (@statements [$($out:tt)*]) => {
+        set_slice! { $($out)* }
+    };
+    (@statements [$($out:tt)*] $($rest:tt)+) => {
+        set_slice_debug!(@statement [$($out)*] [] $($rest)+)
+    };
+    (@statement [$($out:tt)*] [$($statement:tt)*] ; $($rest:tt)*) => {
+        set_slice_debug!(@source [$($out)*] [$($statement)*] $($rest)*)
+    };
+    (@statement [$($out:tt)*] [$($statement:tt)*] $next:tt $($rest:tt)*) => {
+        set_slice_debug!(@statement [$($out)*] [$($statement)* $next] $($rest)*)
+    };
+    // the last instruction has no semicolon, so it may produce a value, and is left as it is
+    (@statement [$($out:tt)*] [$($statement:tt)*]) => {
+        set_slice! { $($out)* $($statement)* }
+    };
+
+    // sources with their own syntax in `set_slice!`, which do not parse as an expression, or mean something else as one
+    (@source [$($out:tt)*] [$slice:ident $([$($range:tt)*])? = $option:ident &$source:ident[max = $len:expr]] $($rest:tt)*) => {
+        set_slice_debug!(@statements [$($out)* $slice $([$($range)*])? = $option &$source[max = $len];] $($rest)*)
+    };
+    (@source [$($out:tt)*] [$slice:ident $([$($range:tt)*])? = $option:ident &$source:ident[min = $len:expr]] $($rest:tt)*) => {
+        set_slice_debug!(@statements [$($out)* $slice $([$($range)*])? = $option &$source[min = $len];] $($rest)*)
+    };
+    (@source [$($out:tt)*] [$slice:ident $([$($range:tt)*])? = $option:ident $source:ident[$($source_range:tt)*]] $($rest:tt)*) => {
+        set_slice_debug!(@statements [$($out)* $slice $([$($range)*])? = $option $source[$($source_range)*];] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = move $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = move $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = copy $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = copy $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = clone $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = clone $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = into $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = into $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = to_owned $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = to_owned $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = from_le $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = from_le $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = from_be $value:expr] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = from_be $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = $value:literal] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = $value] $($rest)*)
+    };
+    (@source $out:tt [$slice:ident $([$($range:tt)*])? = $first:expr, $($value:expr),+] $($rest:tt)*) => {
+        set_slice_debug!(@named $out [$slice $([$($range)*])? = $first, $($value),+] $($rest)*)
+    };
+    (@source [$($out:tt)*] [$($statement:tt)*] $($rest:tt)*) => {
+        set_slice_debug!(@statements [$($out)* $($statement)* ;] $($rest)*)
+    };
+    (@named [$($out:tt)*] [$($statement:tt)*] $($rest:tt)*) => {
+        set_slice_debug!(@statements [$($out)* #[name = stringify!($($statement)*)] $($statement)* ;] $($rest)*)
+    };
+
+    ($($rest:tt)*) => {
+        set_slice_debug!(@statements [] $($rest)*)
+    };
+}
+
+/// a version of `set_slice!` that can be used in a `const fn`, see crate level docs for more info
+#[macro_export]
+macro_rules! const_set_slice {
+    (@bounds $slice:ident []) => { (0, $slice.len()) };
+    (@bounds $slice:ident [..]) => { (0, $slice.len()) };
+    (@bounds $slice:ident [$start:tt ..]) => { ($start, $slice.len()) };
+    (@bounds $slice:ident [.. $end:tt]) => { (0, $end) };
+    (@bounds $slice:ident [..= $end:tt]) => { (0, $end + 1) };
+    (@bounds $slice:ident [$start:tt .. $end:tt]) => { ($start, $end) };
+    (@bounds $slice:ident [$start:tt ..= $end:tt]) => { ($start, $end + 1) };
+    (@bounds $slice:ident [$($range:tt)*]) => {
+        compile_error!(concat!("invalid range ", stringify!($($range)*), ", bounds that are not literals or identifiers must be put in parentheses"))
+    };
+    (@check $slice:ident [$($range:tt)*], $len:expr) => {{
+        let (start, end): (usize, usize) = $crate::const_set_slice!(@bounds $slice [$($range)*]);
+
+        assert!(start <= end && end <= $slice.len(), "const_set_slice: the range is out of bounds of the slice");
+        assert!(end - start == $len, "const_set_slice: the value length does not match the length of the range");
+
+        start
+    }};
+
+    ($slice:ident = move $value:expr; $($rest:tt)*) => {
+        $slice = $value;
+        $crate::const_set_slice!($($rest)*);
+    };
+    ($slice:ident $([$($range:tt)*])? = copy $value:expr; $($rest:tt)*) => {
+        {
+            let value: &[_] = $value;
+            let start = $crate::const_set_slice!(@check $slice [$($($range)*)?], value.len());
+            let mut i = 0;
+
+            while i < value.len() {
+                $slice[start + i] = value[i];
+                i += 1;
+            }
+        }
+        $crate::const_set_slice!($($rest)*);
+    };
+    ($slice:ident $([$($range:tt)*])? = $($value:expr),+; $($rest:tt)*) => {
+        {
+            let mut i = $crate::const_set_slice!(@check $slice [$($($range)*)?], $crate::count!($($value)*));
+
+            $(
+                $slice[i] = $value;
+                i += 1;
+            )+
+            let _ = i;
+        }
+        $crate::const_set_slice!($($rest)*);
+    };
+
+    () => {};
+    ($($rest:tt)+) => {
+        compile_error!("const_set_slice! only supports the list, copy and move instructions, each ending with a semicolon");
+    };
+}
+
+/// a macro for lazily reading parts of slices, see crate level docs for more info
+#[macro_export]
+macro_rules! iter_slice {
+    ($source:ident[$($range:tt)*] map $function:expr) => {
+        iter_slice!($source[$($range)*]).map($function)
+    };
+    ($source:ident[$($range:tt)*]) => {{
+        let source: &[_] = &$source;
+        let (start, end) = match $crate::__resolve_range($($range)*, source.len()) {
+            Some(bounds) => bounds,
+            None => panic!(
+                "source range {}[{}] is out of bounds for length {}",
+                stringify!($source), stringify!($($range)*), source.len()
+            ),
+        };
+
+        source[start..end].iter()
+    }};
+    ($source:ident map $function:expr) => {
+        iter_slice!($source[..] map $function)
+    };
+    ($source:ident) => {
+        iter_slice!($source[..])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(2 + 2, 4);
+    }
+
+    #[test]
+    fn test_move_values() {
+        let mut v = [0; 6];
+        let value = 0;
+        let array = [2, 3]; 
+        let vec = [4, 5, 6];
+
+        set_slice! {
+            v[0..1] = value;
+            v[1..3] = move array;
+            v[3..] = move vec;
+        }
+        // println!("{:?}", vec); // COMPILE ERROR: use after move
+
+        assert_eq!(v, [0, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_full_range() {
+        let mut v = [0; 10];
+
+        set_slice! {
+            v[..] = 0, 1, 2, 3, 4, 5, 6, 7, 8, 9;
+        }
+
+        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut v = [0; 10];
+
+        set_slice! {
+            v = 0, 1, 2, 3, 4, 5, 6, 7, 8, 9;
+        }
+
+        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn set_slice_test() {
+        let mut v = [0; 8];
+        let values = [4, 5, 6];
+        let array = [0, 2];
+        let deref = [7, 8];
+
+        set_slice! {
+            v[1..=2] = copy &[5, 3];
+            v[3..6] = copy &values;
+            v[..2] = copy &array;
+            v[6..] = copy &deref;
+        }
+        let _ = values;
+
+        assert_eq!(v, [0, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn set_slice_test_unsafe() {
+        #[derive(PartialEq, Debug)]
+        struct A(i32);
+        let mut v = [A(0), A(0), A(0), A(0), A(0), A(0), A(0), A(0)];
+        let values = [A(4), A(5), A(6)];
+        let array = [A(0), A(2)];
+        let deref = [A(7), A(8)];
+
+        set_slice! {
+            unsafe v[1..=2]: (2) = ref &[A(5), A(3)];
+            unsafe v[3..6]: (3) = ref &values;
+            unsafe v[..2]: (2) = ref &array;
+            unsafe v[6..]: (2) = ref &deref;
+        }
+        let _ = values;
+
+        assert_eq!(v, [A(0), A(2), A(3), A(4), A(5), A(6), A(7), A(8)]);
+    }
+
+    #[test]
+    fn set_slice_test_unsafe_inferred() {
+        let mut v = [0; 8];
+        let values = [4, 5, 6];
+
+        set_slice! {
+            unsafe v[3..6] = ref &values;
+            unsafe v[6..] = ref &[7, 8];
+            unsafe v[..0] = ref &[];
+        }
+
+        assert_eq!(v, [0, 0, 0, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn set_slice_test_unsafe_inferred_open_ranges() {
+        let mut v = [0; 6];
+        let tail = [5, 6];
+        let n = 2;
+
+        set_slice! {
+            unsafe v[..] = ref &[9; 6];
+            unsafe v[..n] = ref &[1, 2];
+            unsafe v[n..=3] = ref &[3, 4];
+            unsafe v[4..] = ref &tail;
+        }
+
+        assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn set_slice_test_unsafe_inferred_open_range_invalid_length() {
+        let mut v = [0; 5];
+
+        set_slice! {
+            unsafe v[2..] = ref &[1, 2];
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn set_slice_test_unsafe_inferred_invalid_length() {
+        let mut v = [0; 3];
+
+        set_slice! {
+            unsafe v = ref &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut v = [0, 1, 2, 3, 4, 5, 6, 7];
+
+        set_slice! {
+            v[2..6] = reverse;
+        }
+
+        assert_eq!(v, [0, 1, 5, 4, 3, 2, 6, 7]);
+
+        set_slice! {
+            v[..=1] = reverse;
+            v[6..] = reverse;
+        }
+
+        assert_eq!(v, [1, 0, 5, 4, 3, 2, 7, 6]);
+
+        set_slice! {
+            v = reverse;
+        }
+
+        assert_eq!(v, [6, 7, 2, 3, 4, 5, 0, 1]);
+    }
+
+    #[test]
+    fn test_source_range() {
+        let mut v = [0; 4];
+        let source = [1, 2, 3, 4, 5, 6];
+        let start = 2;
+
+        set_slice! {
+            v = copy source[start..start + 4];
+            v[..2] = clone source[..=1];
+        }
+
+        assert_eq!(v, [1, 2, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "source range source[3..7] is out of bounds for length 6")]
+    fn test_source_range_out_of_bounds() {
+        let mut v = [0; 4];
+        let source = [1, 2, 3, 4, 5, 6];
+
+        set_slice! {
+            v = copy source[3..7];
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut v = [0; 7];
+        let left = [1, 3, 3, 8];
+        let right = [2, 3, 9];
+
+        set_slice! {
+            v = merge &left, &right;
+        }
+
+        assert_eq!(v, [1, 2, 3, 3, 3, 8, 9]);
+
+        set_slice! {
+            v[2..] = merge &[], &[4, 5, 6, 7, 8];
+        }
+
+        assert_eq!(v, [1, 2, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice length (7) is invalid, expected: 6")]
+    fn test_merge_invalid_length() {
+        let mut v = [0; 7];
+
+        set_slice! {
+            v = merge &[1, 2, 3], &[4, 5, 6];
+        }
+    }
+
+    #[test]
+    fn test_rotated_left() {
+        let mut v = [0; 7];
+        let source = [1, 2, 3, 4, 5];
+
+        set_slice! {
+            v[1..6] = rotated_left(2) &source;
+        }
+
+        assert_eq!(v, [0, 3, 4, 5, 1, 2, 0]);
+
+        set_slice! {
+            v[1..6] = rotated_left(7) &source;
+            v[..0] = rotated_left(3) &[];
+        }
+
+        assert_eq!(v, [0, 3, 4, 5, 1, 2, 0]);
+        assert_eq!(source, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_checked_list() {
+        let mut v = [0u16; 4];
+        let x = 40;
+
+        set_slice! {
+            v = 0..=255: 10, 20, 30, 255;
+            v[1..] = 0..256: 0x20, 0x30, x;
+        }
+
+        assert_eq!(v, [10, 0x20, 0x30, 40]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "value 1 of the list is out of range 0..=255")]
+    fn test_checked_list_out_of_range() {
+        let mut v = [0u16; 3];
+        let x = 300;
+
+        set_slice! {
+            v = 0..=255: 10, x, 30;
+        }
+    }
+
+    #[test]
+    fn test_iter_slice() {
+        let source = [1, 2, 3, 4, 5, 6];
+        let doubled = iter_slice!(source[2..5] map |x| x * 2);
+        let head = iter_slice!(source[..=1]);
+        let all = iter_slice!(source map |&x| x);
+
+        assert!(doubled.eq([6, 8, 10].iter().cloned()));
+        assert!(head.eq([1, 2].iter()));
+        assert_eq!(all.sum::<i32>(), 21);
+    }
+
+    #[test]
+    #[should_panic(expected = "source range source[4..] is out of bounds for length 3")]
+    fn test_iter_slice_out_of_bounds() {
+        let source = [1, 2, 3];
+
+        let _ = iter_slice!(source[4..]);
+    }
+
+    #[test]
+    fn test_volatile_copy() {
+        let mut regs = [0u32; 4];
+        let values = [0xdead, 0xbeef];
+
+        set_slice! {
+            regs[1..3] = volatile copy &values;
+            regs[3..] = volatile copy &[7];
+        }
+
+        assert_eq!(regs, [0, 0xdead, 0xbeef, 7]);
+    }
+
+    #[test]
+    fn test_dedup_into() {
+        let mut v = [0; 8];
+        let source = [1, 1, 2, 3, 3, 3, 1];
+
+        let n = set_slice!(v = dedup_into &source);
+
+        assert_eq!(n, 4);
+        assert_eq!(v, [1, 2, 3, 1, 0, 0, 0, 0]);
+
+        set_slice! {
+            v[4..] = dedup_into &[5, 5, 5];
+            v[5..] = dedup_into &[];
+        }
+
+        assert_eq!(v, [1, 2, 3, 1, 5, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice length (2) is invalid, expected at least: 3")]
+    fn test_dedup_into_too_small() {
+        let mut v = [0; 2];
+
+        set_slice!(v = dedup_into &[1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut packet = [0u8; 11];
+
+        set_slice! {
+            packet[..5] = copy b"abcde";
+            packet[5..7] = checksum fletcher16 &packet[..5];
+            packet[7..] = checksum crc32 &[];
+        }
+
+        assert_eq!(packet, [b'a', b'b', b'c', b'd', b'e', 0xc8, 0xf0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice length (2) is invalid, expected: 4 (the width of crc32)")]
+    fn test_checksum_invalid_width() {
+        let mut packet = [0u8; 4];
+
+        set_slice! {
+            packet[2..] = checksum crc32 &packet[..2];
+        }
+    }
+
+    #[test]
+    fn test_variant_table() {
+        #[allow(dead_code)]
+        enum Key { A, B = 3, C, D = 7 }
+        let mut table = [0; 8];
+        let mut names = ["?"; 8];
+
+        set_slice! {
+            table = variant_table(Default) { Key::A => 1, Key::C => 3, };
+            names[..] = variant_table("-") { Key::B => "b", Key::D => "d" };
+        }
+
+        assert_eq!(table, [1, 0, 0, 0, 3, 0, 0, 0]);
+        assert_eq!(names, ["-", "-", "-", "b", "-", "-", "-", "d"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "variant Key::B (3) is out of bounds for length 2")]
+    fn test_variant_table_out_of_bounds() {
+        #[allow(dead_code)]
+        enum Key { A, B = 3 }
+        let mut table = [0; 2];
+
+        set_slice! {
+            table = variant_table(Default) { Key::B => 1 };
+        }
+    }
+
+    #[test]
+    fn test_into() {
+        let mut v = [0u64; 4];
+        let values = [1u32, 2, 3];
+
+        set_slice! {
+            v[1..] = into &values;
+            v[..1] = into &[7u8];
+        }
+
+        assert_eq!(v, [7, 1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_to_owned() {
+        use alloc::string::String;
+
+        let mut v = [String::new(), String::new(), String::new()];
+        let refs = ["a", "b"];
+
+        set_slice! {
+            v[1..] = to_owned &refs;
+        }
+
+        assert_eq!(v, ["", "a", "b"]);
+    }
+
+    #[test]
+    fn test_take_forget() {
+        use core::cell::Cell;
+
+        struct Counted<'a>(u8, &'a Cell<usize>);
+
+        impl<'a> Drop for Counted<'a> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut v = [Counted(0, &drops), Counted(0, &drops), Counted(0, &drops)];
+        let source = [Counted(1, &drops), Counted(2, &drops)];
+
+        set_slice! {
+            v[1..] = take_forget source;
+        }
+
+        assert_eq!(drops.get(), 2);
+        assert_eq!([v[0].0, v[1].0, v[2].0], [0, 1, 2]);
+
+        drop(v);
+
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut v = [0.0f32, 1.0, 2.0, 3.0];
+        let offset = 1.0;
+
+        set_slice! {
+            v = map |x| x * 2.0 + offset;
+            v[2..] = map |x| -x;
+        }
+
+        assert_eq!(v, [1.0, 3.0, -5.0, -7.0]);
+    }
+
+    #[test]
+    fn test_return_region() {
+        let mut v = [0, 1, 2, 3, 4, 5];
+
+        {
+            let region = set_slice!(v[1..=3] = reverse);
+
+            assert_eq!(region, [3, 2, 1]);
+            region[0] = 9;
+        }
+
+        let all = set_slice!(v = map |x| x + 1);
+
+        assert_eq!(all, [1, 10, 3, 2, 5, 6]);
+    }
+
+    #[test]
+    fn test_push_window() {
+        let mut window = [1, 2, 3, 4];
+
+        set_slice! {
+            window = push_back 5;
+        }
+
+        assert_eq!(window, [2, 3, 4, 5]);
+
+        set_slice! {
+            window = push_front 0;
+            window[2..] = push_back 9;
+            window[..0] = push_front 7;
+        }
+
+        assert_eq!(window, [0, 2, 4, 9]);
+    }
+
+    #[test]
+    fn test_push_reads_window() {
+        let mut window = [1, 2, 3];
+
+        set_slice! {
+            window = push_back window[0] + 10;
+            window = push_front window[2] * 2;
+        }
+
+        assert_eq!(window, [22, 2, 3]);
+    }
+
+    #[test]
+    fn test_some_all() {
+        let mut opts = [None; 4];
+        let values = [1, 2];
+
+        set_slice! {
+            opts[1..3] = some_all copy &values;
+        }
+
+        assert_eq!(opts, [None, Some(1), Some(2), None]);
+
+        let mut opts = [None, None, Some("c")];
+
+        set_slice! {
+            opts[..2] = some_all clone &["a", "b"];
+            opts[1..] = none_all;
+        }
+
+        assert_eq!(opts, [Some("a"), None, None]);
+    }
+
+    #[test]
+    fn test_try_fill_with() {
+        let mut v = [0u8; 4];
+        let mut next = 250u8;
+
+        let result: Result<(), u8> = set_slice!(v[1..3] = try_fill_with || {
+            next = next.checked_add(2).ok_or(next)?;
+            Ok(next)
+        });
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(v, [0, 252, 254, 0]);
+
+        let result: Result<(), u8> = set_slice!(v = try_fill_with || {
+            next = next.checked_add(2).ok_or(next)?;
+            Ok(next)
+        });
+
+        assert_eq!(result, Err(254));
+        assert_eq!(v, [0, 252, 254, 0]);
+    }
+
+    #[test]
+    fn test_spread() {
+        let mut v = [[0; 2]; 4];
+
+        set_slice! {
+            v[1..] = spread [1, 2];
+            v[..0] = spread [3, 4];
+        }
+
+        assert_eq!(v, [[0, 0], [1, 2], [1, 2], [1, 2]]);
+    }
+
+    #[test]
+    fn test_spread_panic_in_clone() {
+        use core::cell::Cell;
+        use self::std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct Tracked<'a> {
+            id: u8,
+            live: &'a Cell<isize>,
+            clones: &'a Cell<usize>,
+        }
+
+        impl<'a> Tracked<'a> {
+            fn new(id: u8, live: &'a Cell<isize>, clones: &'a Cell<usize>) -> Self {
+                live.set(live.get() + 1);
+                Tracked { id, live, clones }
+            }
+        }
+
+        impl<'a> Clone for Tracked<'a> {
+            fn clone(&self) -> Self {
+                if self.clones.get() == 2 {
+                    panic!("clone failed");
+                }
+
+                self.clones.set(self.clones.get() + 1);
+                Tracked::new(self.id, self.live, self.clones)
+            }
+        }
+
+        impl<'a> Drop for Tracked<'a> {
+            fn drop(&mut self) {
+                self.live.set(self.live.get() - 1);
+            }
+        }
+
+        let (live, clones) = (Cell::new(0), Cell::new(0));
+        let mut v = [
+            Tracked::new(0, &live, &clones),
+            Tracked::new(0, &live, &clones),
+            Tracked::new(0, &live, &clones),
+            Tracked::new(0, &live, &clones),
+        ];
+        let template = Tracked::new(1, &live, &clones);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            set_slice! {
+                v = spread template;
+            }
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(live.get(), 4);
+        assert_eq!([v[0].id, v[1].id, v[2].id, v[3].id], [1, 1, 0, 0]);
+
+        drop(v);
+
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut v = [0; 6];
+        let header = [1, 2, 3, 4, 5];
+        let short = [7, 8];
+
+        set_slice! {
+            v[..4] = copy &header[min = 4];
+            v[3..] = clone &short[max = 3];
+        }
+
+        assert_eq!(v, [1, 2, 3, 7, 8, 0]);
+    }
+
+    #[test]
+    fn test_min_max_locals_in_source_range() {
+        let mut v = [0; 6];
+        let src = [1, 2, 3, 4, 5, 6];
+        let (min, max) = (1, 3);
+
+        set_slice! {
+            v[..2] = copy &src[min..max];
+            v[3..] = copy &src[max..];
+            v[2..3] = clone &src[..min];
+        }
+
+        assert_eq!(v, [2, 3, 1, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value length (3) is invalid, expected at least: 4")]
+    fn test_min_too_short() {
+        let mut v = [0; 4];
+        let values = [1, 2, 3];
+
+        set_slice! {
+            v = copy &values[min = 4];
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "value length (3) is invalid, expected at most: 2")]
+    fn test_max_too_long() {
+        let mut v = [0; 4];
+        let values = [1, 2, 3];
+
+        set_slice! {
+            v = copy &values[max = 2];
+        }
+    }
+
+    #[test]
+    fn test_3d_line() {
+        let mut volume = [0; 2 * 3 * 4];
+        let line = [1, 2, 3, 4];
+
+        set_slice! {
+            volume @ (3, 4) [1, 2, ..] = copy &line;
+            volume @ (3, 4) [0, 1, ..] = 5, 6, 7, 8;
+        }
+
+        assert_eq!(volume[20..], line);
+        assert_eq!(volume[4..8], [5, 6, 7, 8]);
+        assert_eq!(volume.iter().sum::<i32>(), 36);
+    }
+
+    #[test]
+    #[should_panic(expected = "index [0, 3, ..] is out of bounds for dimensions (_, 3, 4) of length 24")]
+    fn test_3d_line_out_of_bounds() {
+        let mut volume = [0; 2 * 3 * 4];
+
+        set_slice! {
+            volume @ (3, 4) [0, 3, ..] = copy &[1, 2, 3, 4];
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 4")]
+    fn test_3d_line_invalid_length() {
+        let mut volume = [0; 2 * 3 * 4];
+
+        set_slice! {
+            volume @ (3, 4) [1, 2, ..] = 1, 2, 3, 4;
+            volume @ (3, 4) [1, 2, ..] = copy &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    fn test_try_set_slice() {
+        let mut v = [0; 6];
+        let values = [4, 5];
+
+        let result = try_set_slice! {
+            v[..2] = 1, 2;
+            v[2..4] = move [3, 4];
+            v[4..] = copy &values;
+            v[4..] = clone &[6, 7, 8];
+            v = 9, 9, 9, 9, 9, 9;
+        };
+
+        assert_eq!(result, Err(::SetSliceError { line: 4, statement_index: 3, kind: ::SetSliceErrorKind::Length, expected: 2, actual: 3 }));
+        assert_eq!(v, [1, 2, 3, 4, 4, 5]);
+
+        let result = try_set_slice! {
+            v = 0, 0, 0, 0, 0, 0;
+        };
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(v, [0; 6]);
+    }
+
+    #[test]
+    fn test_from_deque() {
+        use self::std::collections::VecDeque;
+
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend([3, 4, 5, 6].iter().cloned());
+        deque.pop_front();
+        deque.pop_front();
+        deque.extend([7, 8].iter().cloned());
+
+        let mut v = [0; 6];
+
+        set_slice! {
+            v[2..] = from_deque &deque;
+        }
+
+        assert_eq!(v, [0, 0, 5, 6, 7, 8]);
+
+        let mut names = [""; 2];
+        let deque: VecDeque<_> = ["a", "b"].iter().cloned().collect();
+
+        set_slice! {
+            names = from_deque clone &deque;
+        }
+
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_with_stats() {
+        let mut v = [0; 5];
+        let source = [4, -2, 7, 1];
+
+        let stats = set_slice!(with_stats v[1..] = copy &source);
+
+        assert_eq!(stats, Some(::SliceStats { min: -2, max: 7, sum: 10 }));
+        assert_eq!(v, [0, 4, -2, 7, 1]);
+
+        let stats = set_slice!(with_stats v[..0] = copy &[]);
+
+        assert_eq!(stats, None);
+    }
+
+    #[test]
+    fn test_fma() {
+        let mut acc = [0.0f32; 3];
+        let a = [1.0, 2.0, 3.0];
+        let b = [0.5, 0.25, 0.125];
+
+        set_slice! {
+            acc = fma &a, &b, 2.0;
+        }
+
+        assert_eq!(acc, [2.5, 4.25, 6.125]);
+
+        set_slice! {
+            acc[1..] = fma &a[1..], &a[1..];
+        }
+
+        assert_eq!(acc, [2.5, 8.25, 15.125]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value lengths (2, 3) are invalid, expected: 3")]
+    fn test_fma_invalid_length() {
+        let mut acc = [0.0f64; 3];
+
+        set_slice! {
+            acc = fma &[1.0, 2.0], &[1.0, 2.0, 3.0], 1.0;
+        }
+    }
+
+    #[test]
+    fn test_arr_copy() {
+        let mut dst = [0u32; 5];
+        let src = [1, 2, 3, 4, 5];
+
+        set_slice! {
+            dst = arr_copy &src;
+        }
+
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    fn test_arr_copy_behind_references() {
+        fn copy(mut dst: &mut [u8; 64], src: &[u8; 64]) {
+            set_slice! {
+                dst = arr_copy src;
+            }
+        }
+
+        let mut dst = [0; 64];
+        let src = [7; 64];
+
+        copy(&mut dst, &src);
+        assert_eq!(dst[..], src[..]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_runs() {
+        use alloc::vec::Vec;
+
+        let src = [1, 1, 2, 3, 3, 3, 1];
+        let mut out = Vec::new();
+        let mut lens = Vec::new();
+
+        set_slice! {
+            out = runs &src |a, b| a == b map |run: &[i32]| (run[0], run.len());
+            lens = runs &src[2..] |a, b| a == b map |run| run.len();
+        }
+
+        assert_eq!(out, [(1, 2), (2, 1), (3, 3), (1, 1)]);
+        assert_eq!(lens, [1, 3, 1]);
+
+        set_slice! {
+            lens = runs &src[..0] |a, b| a == b map |run: &[i32]| run.len();
+        }
+
+        assert!(lens.is_empty());
+    }
+
+    #[test]
+    fn test_list_of_references() {
+        let (x, y) = (1, 2);
+        let mut a = [&0; 2];
+        let mut b = [&0; 1];
+
+        set_slice! {
+            a = &x, &y;
+            b = move [&x];
+        }
+
+        assert_eq!(a, [&1, &2]);
+        assert_eq!(b, [&1]);
+    }
+
+    #[test]
+    fn test_list_of_single_reference() {
+        let (x, xs) = (3u8, [1u8, 2]);
+        let mut refs = [&0u8; 2];
+        let mut slices: [&[u8]; 2] = [&[], &[]];
+        let start = 1;
+
+        set_slice! {
+            refs[..1] = &x;
+            refs[start..] = &xs[1];
+            slices[start..] = &xs[..1];
+        }
+
+        assert_eq!(refs, [&3, &2]);
+        assert_eq!(slices, [&[][..], &[1][..]]);
+    }
+
+    #[test]
+    fn test_extend() {
+        use self::std::vec::Vec;
+
+        let mut buf = Vec::with_capacity(2);
+        let more = [1, 2, 3];
+        let exact = [4, 5, 6, 7, 8];
+
+        set_slice! {
+            buf = extend &more;
+            buf = extend &more[1..];
+            buf = copy &exact;
+        }
+
+        assert_eq!(buf, [4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_extend_to_fit() {
+        use self::std::string::String;
+        use self::std::vec;
+        use self::std::vec::Vec;
+
+        let mut nums = Vec::new();
+        let mut names = vec![String::from("x"); 3];
+
+        set_slice! {
+            nums = extend copy &[1, 2, 3];
+            nums = extend copy &[4, 5];
+            nums = extend copy &[];
+            names = extend clone &[String::from("a"), String::from("b")];
+        }
+
+        assert_eq!(nums, [4, 5, 3]);
+        assert_eq!(names, ["a", "b", "x"]);
+
+        set_slice! {
+            names = extend clone &[String::new(), String::new(), String::new(), String::from("d")];
+        }
+
+        assert_eq!(names, ["", "", "", "d"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 5")]
+    fn test_extend_then_copy_invalid_length() {
+        use self::std::vec::Vec;
+
+        let mut buf = Vec::new();
+        let more = [1, 2, 3, 4, 5];
+
+        set_slice! {
+            buf = extend &more;
+            buf = copy &more[..3];
+        }
+    }
+
+    #[test]
+    fn test_parity() {
+        let mut frame = [0x12u8, 0x34, 0x56, 0];
+        let mut words = [0xff00u16, 0x0ff0, 0];
+        let last = frame.len() - 1;
+
+        set_slice! {
+            frame[last..] = parity &frame[..last];
+            words[2..] = parity &words[..2];
+        }
+
+        assert_eq!(frame, [0x12, 0x34, 0x56, 0x12 ^ 0x34 ^ 0x56]);
+        assert_eq!(words[2], 0xf0f0);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (2) is invalid, expected: 1")]
+    fn test_parity_invalid_length() {
+        let mut frame = [1u8, 2, 3, 0, 0];
+
+        set_slice! {
+            frame[3..] = parity &frame[..3];
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        let mut buf = [0; 4];
+        let mut frame = [0; 5];
+        let tail = [1, 2, 3];
+
+        set_slice! {
+            buf => (head, rest) = (9, copy &tail);
+            frame[1..] => (rest, last) = (copy &tail, 7);
+        }
+
+        assert_eq!(buf, [9, 1, 2, 3]);
+        assert_eq!(frame, [0, 1, 2, 3, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 2")]
+    fn test_split_invalid_length() {
+        let mut buf = [0; 3];
+        let tail = [1, 2, 3];
+
+        set_slice! {
+            buf => (head, rest) = (9, copy &tail[..2]);
+            buf => (head, rest) = (9, copy &tail);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (0) is invalid, expected at least: 1")]
+    fn test_split_empty() {
+        let mut buf = [0; 3];
+        let tail: [i32; 0] = [];
+
+        set_slice! {
+            buf[..0] => (rest, last) = (copy &tail, 9);
+        }
+    }
+
+    #[test]
+    fn test_fence() {
+        let mut buf = [0; 3];
+        let src = [1, 2, 3];
+
+        set_slice! {
+            buf = copy &src;
+            fence(Release);
+            atomic fence(SeqCst);
+            buf[..1] = 7;
+            fence(Acquire);
+        }
+
+        assert_eq!(buf, [7, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3: value length (2) is invalid, expected: 3")]
+    fn test_fence_keeps_line_numbers() {
+        let mut buf = [0; 3];
+        let src: &[i32] = &[1, 2];
+
+        set_slice! {
+            fence(Release);
+            atomic fence(AcqRel);
+            buf = copy src;
+        }
+    }
+
+    #[test]
+    fn test_permute() {
+        let src = [10, 20, 30, 40];
+        let perm = [3, 0, 0, 2];
+        let mut out = [0; 4];
+        let mut names = [""; 4];
+
+        set_slice! {
+            out = permute(&perm) copy &src;
+            names[1..] = permute(&perm[1..]) clone &["a", "b", "c"];
+        }
+
+        assert_eq!(out, [40, 10, 10, 30]);
+        assert_eq!(names, ["", "a", "a", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: permutation index perm[2] (4) is out of bounds for length 4")]
+    fn test_permute_out_of_bounds() {
+        let src = [10, 20, 30, 40];
+        let perm = [3, 0, 4];
+        let mut out = [0; 3];
+
+        set_slice! {
+            out = permute(&perm) copy &src;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: permutation length (2) is invalid, expected: 3")]
+    fn test_permute_invalid_length() {
+        let src = [10, 20, 30, 40];
+        let mut out = [0; 3];
+
+        set_slice! {
+            out = permute(&[0, 1]) copy &src;
+        }
+    }
+
+    #[test]
+    fn test_scatter_permute() {
+        let src = [10, 20, 30, 40];
+        let perm = [2, 0, 3, 1];
+        let mut sorted = [0; 4];
+        let mut unsorted = [0; 4];
+
+        set_slice! {
+            sorted = permute(&perm) copy &src;
+            unsorted = scatter_permute(&perm) copy &sorted;
+        }
+
+        assert_eq!(sorted, [30, 10, 40, 20]);
+        assert_eq!(unsorted, src);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: permutation index perm[1] (3) is out of bounds for length 3")]
+    fn test_scatter_permute_out_of_bounds() {
+        let mut out = [0; 4];
+
+        set_slice! {
+            out[1..] = scatter_permute(&[0, 3, 1]) clone &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "line 1: permutation index perm[2] (0) is a duplicate of perm[0]")]
+    fn test_scatter_permute_duplicate() {
+        let mut out = [0; 3];
+
+        set_slice! {
+            out = scatter_permute(&[0, 1, 0]) copy &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    fn test_copy_until() {
+        let src = *b"abc\0def";
+        let mut name = [b'-'; 5];
+        let mut short = [b'-'; 2];
+
+        let n = set_slice!(name = copy_until(0) &src);
+        let m = set_slice!(short = copy_until(0, truncate) &src);
+
+        assert_eq!((n, &name), (3, b"abc--"));
+        assert_eq!((m, &short), (2, b"ab"));
+
+        set_slice! {
+            name[1..] = copy_until(0) &src[4..];
+            short = copy_until(0, truncate) &src[3..];
+        }
+
+        assert_eq!(&name, b"adef-");
+        assert_eq!(&short, b"ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (2) is invalid, expected at least: 3")]
+    fn test_copy_until_too_small() {
+        let mut short = [0; 2];
+
+        set_slice!(short = copy_until(0) &[1, 2, 3, 0, 4]);
+    }
+
+    #[test]
+    fn test_transpose_bits8() {
+        let src = [0x80, 0xc0, 0xa5, 0x01, 0xff, 0x3c, 0x00, 0x42];
+        let mut dst = [0; 8];
+        let mut back = [0; 8];
+
+        set_slice! {
+            dst = transpose_bits8 &src;
+            back = transpose_bits8 &dst;
+        }
+
+        for (i, row) in src.iter().enumerate() {
+            for (j, column) in dst.iter().enumerate() {
+                assert_eq!((column >> (7 - i)) & 1, (row >> (7 - j)) & 1);
+            }
+        }
+
+        assert_eq!(back, src);
+    }
+
+    #[test]
+    fn test_fill_copy() {
+        use core::cell::Cell;
+
+        let calls = Cell::new(0);
+        let make = || {
+            calls.set(calls.get() + 1);
+            0xab_u8
+        };
+        let mut buf = [0; 6];
+
+        set_slice! {
+            buf = fill_copy make();
+            buf[4..] = fill_copy 1;
+        }
+
+        assert_eq!(buf, [0xab, 0xab, 0xab, 0xab, 1, 1]);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_cover() {
+        let mut buf = [0; 8];
+        let header = [1, 2, 3];
+
+        set_slice! {
+            cover: buf => {
+                [0..3] = copy &header;
+                [3..4] = 9;
+                [4..8] = reverse;
+            }
+            buf[4..] = 5, 6, 7, 8;
+        }
+
+        assert_eq!(buf, [1, 2, 3, 9, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (8) is invalid, expected the ranges to end at: 6")]
+    fn test_cover_too_short() {
+        let mut buf = [0; 8];
+
+        set_slice! {
+            cover: buf => {
+                [0..2] = 1, 2;
+                [2..6] = 3, 4, 5, 6;
+            };
+        }
+    }
+
+    #[test]
+    fn test_from_le_be() {
+        let le = [0x0102_0304u32.to_le(), 0xaabb_ccddu32.to_le()];
+        let be = [0x0102_0304u32.to_be(), 0xaabb_ccddu32.to_be()];
+        let mut a = [0; 2];
+        let mut b = [0; 3];
+
+        set_slice! {
+            a = from_le &le;
+            b[1..] = from_be &be;
+        }
+
+        assert_eq!(a, [0x0102_0304, 0xaabb_ccdd]);
+        assert_eq!(b, [0, 0x0102_0304, 0xaabb_ccdd]);
+    }
+
+    #[test]
+    fn test_copy_swapped() {
+        // the path taken on targets whose byte order does not match
+        let mut words = [0i16; 2];
+
+        super::__copy_swapped(&mut words, &[0x0102, -2]);
+
+        assert_eq!(words, [0x0201, (-2i16).swap_bytes()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_from_le_invalid_length() {
+        let mut a = [0u16; 2];
+        let src: &[u16] = &[1];
+
+        set_slice! {
+            a = from_le src;
+        }
+    }
+
+    #[test]
+    fn test_clamp_copy() {
+        let src = [-5, 3, 12, 7];
+        let lo = [0, 0, 0, 8];
+        let hi = [10, 2, 10, 9];
+        let mut out = [0; 4];
+        let mut part = [1; 3];
+
+        set_slice! {
+            out = clamp_copy &src, &lo, &hi;
+            part[1..] = clamp_copy &src[2..], &lo[2..], &hi[2..];
+        }
+
+        assert_eq!(out, [0, 2, 10, 8]);
+        assert_eq!(part, [1, 10, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: high bound length (3) is invalid, expected: 4")]
+    fn test_clamp_copy_invalid_length() {
+        let mut out = [0; 4];
+
+        set_slice! {
+            out = clamp_copy &[1, 2, 3, 4], &[0; 4], &[9; 3];
+        }
+    }
+
+    #[test]
+    fn test_move_default() {
+        use self::std::string::String;
+
+        let mut front = [String::new(), String::new()];
+        let mut back = [String::from("a"), String::from("b")];
+        let pending: &mut [u8] = &mut [1, 2, 3];
+        let mut sent = [0; 4];
+
+        set_slice! {
+            front = move_default back;
+            sent[1..] = move_default pending;
+        }
+
+        assert_eq!(front, ["a", "b"]);
+        assert_eq!(back, ["", ""]);
+        assert_eq!(sent, [0, 1, 2, 3]);
+        assert_eq!(pending, [0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn test_move_default_invalid_length() {
+        let mut a = [0; 3];
+        let mut b = [1; 2];
+
+        set_slice! {
+            a = move_default b;
+        }
+    }
+
+    #[test]
+    fn test_with_count() {
+        let mask = [true, false, true, true, false];
+        let mut data = [1, 2, 3, 4, 5];
+
+        let n = set_slice!(with_count data[mask] = fill 0);
+
+        assert_eq!(n, 3);
+        assert_eq!(data, [0, 2, 0, 0, 5]);
+
+        let n = set_slice!(with_count data[mask] = fill 0);
+
+        assert_eq!(n, 3);
+
+        let n = set_slice!(with_count data[&mask[..]] = copy &[7, 8, 9]);
+
+        assert_eq!(n, 3);
+        assert_eq!(data, [7, 2, 8, 9, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3 (the number of masked elements)")]
+    fn test_with_count_invalid_length() {
+        let mask = [true, false, true, true];
+        let mut data = [0; 4];
+
+        set_slice!(with_count data[mask] = copy &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: mask length (3) is invalid, expected: 4")]
+    fn test_with_count_invalid_mask() {
+        let mut data = [0; 4];
+
+        set_slice!(with_count data[[true; 3]] = fill 1);
+    }
+
+    #[test]
+    fn test_negated_mask() {
+        let keep = [true, false, false, true];
+        let mut data = [1, 2, 3, 4];
+
+        set_slice! {
+            data[!keep] = copy &[8, 9];
+        }
+
+        assert_eq!(data, [1, 8, 9, 4]);
+
+        let n = set_slice!(with_count data[!keep] = fill 0);
+
+        assert_eq!(n, 2);
+        assert_eq!(data, [1, 0, 0, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 1 (the number of masked elements)")]
+    fn test_negated_mask_invalid_length() {
+        let keep = [true, false, true];
+        let mut data = [0; 3];
+
+        set_slice! {
+            data[!keep] = copy &[1];
+            data[!keep] = copy &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    fn test_rect() {
+        let mut img = [0u8; 4 * 3];
+        let sprite = [1, 2, 3, 4];
+
+        set_slice! {
+            img @ (4) rect(1, 0, 3, 2) = fill 9;
+            img @ (4) rect(2, 1, 2, 2) = copy &sprite;
+            img @ (4) rect(0, 3, 4, 0) = fill 7;
+        }
+
+        assert_eq!(img, [
+            0, 9, 9, 9,
+            0, 9, 1, 2,
+            0, 0, 3, 4,
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: rect(3, 0, 2, 1) is out of bounds for width 4 and length 12")]
+    fn test_rect_out_of_bounds() {
+        let mut img = [0u8; 4 * 3];
+
+        set_slice! {
+            img @ (4) rect(0, 2, 4, 1) = fill 1;
+            img @ (4) rect(3, 0, 2, 1) = fill 1;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (3) is invalid, expected: 4 (W * H)")]
+    fn test_rect_invalid_length() {
+        let mut img = [0u8; 4 * 3];
+
+        set_slice! {
+            img @ (4) rect(0, 0, 2, 2) = copy &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut pkt = [0u8; 10];
+        let header = [0xaa];
+        let kind = 0x0102u16;
+
+        set_slice! {
+            @cursor pkt:
+            write &header;
+            write be u16 &kind;
+            write le u16 &kind;
+        }
+
+        assert_eq!(pkt[..5], [0xaa, 0x01, 0x02, 0x02, 0x01]);
+
+        let end = set_slice! {
+            @cursor pkt:
+            write &[1, 2];
+            write &[3; 8]
+        };
+
+        assert_eq!(end, 10);
+        assert_eq!(pkt, [1, 2, 3, 3, 3, 3, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3: write of length 4 at cursor 3 is out of bounds for length 6")]
+    fn test_cursor_overflow() {
+        let mut pkt = [0u8; 6];
+
+        set_slice! {
+            @cursor pkt:
+            write &[1, 2];
+            write &[3];
+            write le u32 &7;
+        }
+    }
+
+    #[test]
+    fn test_saturating_add() {
+        let mut mix = [0i16; 4];
+        let a = [20_000, -20_000, 100, i16::MAX];
+        let b = [20_000, -20_000, -300, 0];
+
+        set_slice! {
+            mix = saturating_add copy &a;
+            mix = saturating_add copy &b;
+            mix[2..3] = saturating_add copy &[1];
+        }
+
+        assert_eq!(mix, [i16::MAX, i16::MIN, -199, i16::MAX]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_saturating_add_invalid_length() {
+        let mut mix = [0u8; 2];
+
+        set_slice! {
+            mix = saturating_add copy &[1];
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_try_extend() {
+        use alloc::vec::Vec;
+
+        let mut v = Vec::new();
+        let result = try_set_slice! {
+            v = extend &[1, 2];
+            v = extend &[3];
+        };
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(v, [1, 2, 3]);
+
+        // zero sized values never allocate, so only the capacity can overflow
+        let mut units = Vec::new();
+        let huge = [(); usize::MAX];
+        let result = try_set_slice! {
+            units = extend &[()];
+            units = extend &huge;
+        };
+
+        assert_eq!(result.map_err(|e| (e.statement_index, e.kind)), Err((1, ::SetSliceErrorKind::Alloc)));
+        assert_eq!(units.len(), 1);
+    }
+
+    #[test]
+    fn test_pack() {
+        let words = [0x0102u16, 0xa0b0];
+        let mut bytes = [0u8; 10];
+
+        set_slice! {
+            bytes[..4] = pack be u16 &words;
+            bytes[4..] = pack le i16 &[-2, 0x0304, 5];
+        }
+
+        assert_eq!(bytes, [0x01, 0x02, 0xa0, 0xb0, 0xfe, 0xff, 0x04, 0x03, 0x05, 0x00]);
+
+        set_slice! {
+            bytes[2..] = pack be u64 &[0x1122_3344_5566_7788];
+        }
+
+        assert_eq!(bytes[2..], [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (5) is invalid, expected: 4 (2 bytes per value)")]
+    fn test_pack_invalid_length() {
+        let mut bytes = [0u8; 5];
+
+        set_slice! {
+            bytes = pack be u16 &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_named() {
+        let mut header = [0; 4];
+        let mut body = [0; 2];
+
+        set_slice! {
+            #[name = "header"] header = 1, 2, 3, 4;
+            #[name = "body"] body = copy &[5, 6];
+            #[name = "tail"] header[2..] = move [7, 8];
+        }
+
+        assert_eq!(header, [1, 2, 7, 8]);
+        assert_eq!(body, [5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 (packet_header): value length (3) is invalid, expected: 4")]
+    fn test_named_list_invalid_length() {
+        let mut header = [0; 4];
+
+        set_slice! {
+            header = 1, 2, 3, 4;
+            #[name = "packet_header"] header = 1, 2, 3;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1 (body): value length (1) is invalid, expected: 2")]
+    fn test_named_copy_invalid_length() {
+        let mut body = [0; 3];
+
+        set_slice! {
+            #[name = "body"] body[1..] = copy &[5];
+        }
+    }
+
+    #[test]
+    fn test_deplane() {
+        let rgba = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let mut red = [0; 3];
+        let mut alpha = [0; 4];
+
+        set_slice! {
+            red = deplane(4, 0) &rgba;
+            alpha[1..] = deplane(4, 3) &rgba;
+        }
+
+        assert_eq!(red, [1, 5, 9]);
+        assert_eq!(alpha, [0, 4, 8, 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (4) is invalid, expected: 3 (the plane length)")]
+    fn test_deplane_invalid_length() {
+        let rgba = [0u8; 12];
+        let mut red = [0; 4];
+
+        set_slice! {
+            red = deplane(4, 0) &rgba;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: offset (4) is invalid, expected less than the stride: 4")]
+    fn test_deplane_invalid_offset() {
+        let rgba = [0u8; 12];
+        let mut red = [0; 3];
+
+        set_slice! {
+            red = deplane(4, 4) &rgba;
+        }
+    }
+
+    #[test]
+    fn test_replane() {
+        let rgba = [1, 2, 3, 4, 5, 6, 7, 8];
+        let (mut r, mut g, mut b, mut a) = ([0; 2], [0; 2], [0; 2], [0; 2]);
+        let mut packed = [0; 8];
+
+        set_slice! {
+            r = deplane(4, 0) &rgba;
+            g = deplane(4, 1) &rgba;
+            b = deplane(4, 2) &rgba;
+            a = deplane(4, 3) &rgba;
+            packed = replane(4, 0) copy &r;
+            packed = replane(4, 1) copy &g;
+            packed = replane(4, 2) clone &b;
+            packed = replane(4, 3) copy &a;
+        }
+
+        assert_eq!(packed, rgba);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (6) is invalid, expected: 8 (the packed length)")]
+    fn test_replane_invalid_length() {
+        let mut packed = [0u8; 6];
+
+        set_slice! {
+            packed = replane(4, 0) copy &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut short = [0; 2];
+        let mut long = [0; 5];
+        let src = [1, 2, 3];
+
+        set_slice! {
+            short = truncate copy &src;
+            long = truncate copy &src;
+            long[3..] = truncate clone &[];
+        }
+
+        assert_eq!(short, [1, 2]);
+        assert_eq!(long, [1, 2, 3, 0, 0]);
+
+        let written = set_slice!(long[1..] = truncate copy &src);
+        assert_eq!(written, 3);
+        assert_eq!(long, [1, 1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_fill() {
+        use self::std::string::String;
+
+        let mut v = [0; 10];
+        let mut names = [String::new(), String::new(), String::new()];
+
+        set_slice! {
+            v = fill 1;
+            v[2..8] = fill 2;
+            v[1..=3] = fill 3;
+            names[1..] = fill String::from("x");
+        }
+
+        assert_eq!(v, [1, 3, 3, 3, 2, 2, 2, 2, 1, 1]);
+        assert_eq!(names, ["", "x", "x"]);
+
+        let region = set_slice!(v[..2] = fill 4);
+
+        region[0] = 5;
+        assert_eq!(v[..3], [5, 4, 3]);
+        assert_eq!(set_slice!(v = fill 0).len(), 10);
+    }
+
+    #[test]
+    fn test_copy_fill() {
+        let mut buf = [1u8; 8];
+
+        set_slice! {
+            buf[2..6] = copy_fill 0;
+            buf[6..=7] = copy_fill 9;
+        }
+
+        assert_eq!(buf, [1, 1, 0, 0, 0, 0, 9, 9]);
+    }
+
+    #[test]
+    fn test_iter_ref() {
+        use self::std::collections::BTreeMap;
+
+        let mut map = BTreeMap::new();
+        map.insert(1, 'a');
+        map.insert(2, 'b');
+        map.insert(3, 'c');
+
+        let mut buf = ['-'; 4];
+        let mut values = map.values();
+
+        set_slice! {
+            buf[..2] = iter_ref &mut values;
+            buf[3..] = iter_ref values;
+        }
+
+        assert_eq!(buf, ['a', 'b', '-', 'c']);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: iterator length (2) is invalid, expected: 3")]
+    fn test_iter_ref_too_short() {
+        let mut buf = [0; 3];
+
+        set_slice! {
+            buf = iter_ref &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_ramp() {
+        let mut gains = [0.0f32; 5];
+        let mut envelope = [9.0; 6];
+
+        set_slice! {
+            gains = ramp(2.0, 4.0);
+            envelope[..3] = fade_in;
+            envelope[3..] = fade_out;
+        }
+
+        assert_eq!(gains, [2.0, 2.5, 3.0, 3.5, 4.0]);
+        assert_eq!(envelope, [0.0, 0.5, 1.0, 1.0, 0.5, 0.0]);
+
+        set_slice! {
+            gains[..1] = ramp(7.0, 8.0);
+            gains[..0] = fade_out;
+        }
+
+        assert_eq!(gains[0], 7.0);
+    }
+
+    #[test]
+    fn test_window() {
+        fn check(actual: &[f64], expected: &[f64]) {
+            assert_eq!(actual.len(), expected.len());
+
+            for (&a, &e) in actual.iter().zip(expected) {
+                assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+            }
+        }
+
+        let mut hann = [0.0; 5];
+        let mut hamming = [0.0; 5];
+        let mut blackman = [0.0; 3];
+        let mut single = [0.0f32; 1];
+
+        set_slice! {
+            hann = window(hann);
+            hamming = window(hamming);
+            blackman = window(blackman);
+            single = window(hann);
+            single[..0] = window(hamming);
+        }
+
+        check(&hann, &[0.0, 0.5, 1.0, 0.5, 0.0]);
+        check(&hamming, &[0.08, 0.54, 1.0, 0.54, 0.08]);
+        check(&blackman, &[0.0, 1.0, 0.0]);
+        assert_eq!(single, [1.0]);
+    }
+
+    #[test]
+    fn test_set_slice_error_display() {
+        use self::std::string::ToString;
+
+        let mut a = [0; 2];
+
+        let result = try_set_slice! {
+            a = copy &[1, 2];
+            a[1..] = 3, 4;
+        };
+
+        let e = result.unwrap_err();
+
+        assert_eq!(e.line, 2);
+        assert_eq!(e.to_string(), "line 2: value length (2) is invalid, expected: 1");
+    }
+
+    // the `Display` of `SetSliceError` must match the panic of the same instructions in `set_slice!`
+    #[test]
+    fn test_set_slice_error_display_matches_panic() {
+        use self::std::string::ToString;
+
+        let a: &mut [i32] = &mut [0; 3];
+
+        let copied = try_set_slice! { a = 1, 2, 3; a[1..] = copy &[1, 2, 3]; };
+        let moved = try_set_slice! { a[..2] = move [1]; };
+
+        assert_eq!(copied.unwrap_err().to_string(), "line 2: value length (3) is invalid, expected: 2");
+        assert_eq!(moved.unwrap_err().to_string(), "line 1: value length (1) is invalid, expected: 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 2")]
+    fn test_set_slice_error_display_matches_copy_panic() {
+        let a: &mut [i32] = &mut [0; 3];
+
+        set_slice! { a = 1, 2, 3; a[1..] = copy &[1, 2, 3]; }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_set_slice_error_display_matches_move_panic() {
+        let a: &mut [i32] = &mut [0; 3];
+
+        set_slice! { a[..2] = move [1]; }
+    }
+
+    #[test]
+    fn test_copy_field() {
+        let mut rows = [(1, 'a'), (2, 'b'), (3, 'c')];
+        let update = [(0, 'x'), (0, 'y'), (0, 'z')];
+        let ids = [(7u8, 70), (8, 80)];
+
+        set_slice! {
+            rows.1 = copy_field &update.1;
+            rows[1..].0 = copy_field &ids[..].1;
+        }
+
+        assert_eq!(rows, [(1, 'x'), (70, 'y'), (80, 'z')]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn test_copy_field_invalid_length() {
+        let mut rows = [(1, 'a'), (2, 'b'), (3, 'c')];
+        let update = [(0, 'x'), (0, 'y')];
+
+        set_slice! {
+            rows.1 = copy_field &update.1;
+        }
+    }
+
+    #[test]
+    fn test_unpack() {
+        let bytes = [0x01, 0x02, 0xa0, 0xb0, 0xfe, 0xff, 0x04, 0x03, 0x05, 0x00];
+        let mut words = [0u16; 2];
+        let mut signed = [0i16; 4];
+
+        set_slice! {
+            words = unpack be u16 &bytes[..4];
+            signed[1..] = unpack le i16 &bytes[4..];
+        }
+
+        assert_eq!(words, [0x0102, 0xa0b0]);
+        assert_eq!(signed, [0, -2, 0x0304, 5]);
+
+        let mut long = [0u64; 1];
+
+        set_slice! {
+            long = unpack be u64 &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88];
+        }
+
+        assert_eq!(long, [0x1122_3344_5566_7788]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (5) is invalid, expected: 4 (2 bytes per value)")]
+    fn test_unpack_invalid_length() {
+        let mut words = [0u16; 2];
+
+        set_slice! {
+            words = unpack le u16 &[1, 2, 3, 4, 5];
+        }
+    }
+
+    #[test]
+    fn test_with() {
+        let mut ramp = [0u8; 4];
+        let mut sub = [9; 6];
+        let mut calls = 0;
+
+        set_slice! {
+            ramp = with |i| i as u8 * 2;
+            sub[2..5] = with |i| { calls += 1; i * 10 };
+        }
+
+        assert_eq!(ramp, [0, 2, 4, 6]);
+        assert_eq!(sub, [9, 9, 0, 10, 20, 9]);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut buf = [0; 5];
+
+        set_slice! {
+            buf[1..4] = iter (1..4).map(|x| x * x);
+            buf[4..] = iter Some(7);
+        }
+
+        assert_eq!(buf, [0, 1, 4, 9, 7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: iterator length (2) is invalid, expected: 3")]
+    fn test_iter_too_short() {
+        let mut buf = [0; 3];
+
+        set_slice! {
+            buf = iter 0..3;
+            buf = iter 0..2;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: iterator length (more than 3) is invalid, expected: 3")]
+    fn test_iter_too_long() {
+        let mut buf = [0; 3];
+
+        set_slice! {
+            buf = iter 0..4;
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_diff_log() {
+        let mut buf = [1, 2, 3, 4, 5];
+
+        let log = set_slice!(diff_log buf[1..] = copy &[2, 0, 4, 9]);
+
+        assert_eq!(buf, [1, 2, 0, 4, 9]);
+        assert_eq!(log, [(1, 3, 0), (3, 5, 9)]);
+
+        let log = set_slice!(diff_log buf = copy &[1, 2, 0, 4, 9]);
+
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn test_compound_overflow() {
+        let mut acc = [250u8, 10, 0];
+        let mut sat = [250u8, 10, 0];
+        let mut product = [-100i8, 3, 2];
+
+        set_slice! {
+            acc =wrapping+= copy &[10, 10, 10];
+            sat[..2] =saturating+= copy &[10, 10];
+            product =saturating*= copy &[2, 2, 2];
+        }
+
+        assert_eq!(acc, [4, 20, 10]);
+        assert_eq!(sat, [255, 20, 0]);
+        assert_eq!(product, [-128, 6, 4]);
+
+        set_slice! {
+            acc =checked+= copy &[1, 2, 3];
+            product[1..] =wrapping*= copy &[100, 100];
+            product[..1] =checked*= copy &[1];
+        }
+
+        assert_eq!(acc, [5, 22, 13]);
+        assert_eq!(product, [-128, 88, -112]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: addition overflowed at index 1")]
+    fn test_compound_checked_overflow() {
+        let mut acc = [0u8, 200, 0];
+
+        set_slice! {
+            acc =checked*= copy &[1, 1, 1];
+            acc =checked+= copy &[1, 100, 1];
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut a = [1, 2, 3, 4];
+        let mut b = [5, 6];
+
+        set_slice! {
+            a[..2] <=> b;
+            a[2..] <=> a[..2];
+        }
+
+        assert_eq!(a, [3, 4, 5, 6]);
+        assert_eq!(b, [1, 2]);
+
+        set_slice! {
+            b <=> a[1..3];
+            a[..0] <=> b[2..];
+        }
+
+        assert_eq!(a, [3, 1, 2, 6]);
+        assert_eq!(b, [4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: the slices overlap, so they can not be swapped")]
+    fn test_swap_overlapping() {
+        let mut a = [1, 2, 3, 4];
+
+        set_slice! {
+            a[..3] <=> a[1..];
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (3) is invalid, expected: 2")]
+    fn test_swap_invalid_length() {
+        let mut a = [1, 2];
+        let mut b = [3, 4, 5];
+
+        set_slice! {
+            a <=> b;
+        }
+    }
+
+    // the length check of `<=>` guards a raw swap, so `debug_checks` must not turn it off in release builds
+    #[test]
+    #[cfg(all(feature = "debug_checks", not(debug_assertions)))]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_swap_invalid_length_debug_checks_release() {
+        let mut a = [1, 2];
+        let mut b = [3];
+
+        set_slice! {
+            a <=> b;
+        }
+    }
+
+    #[test]
+    fn test_nested_col_row() {
+        use self::std::vec;
+
+        let mut grid = [[0; 3]; 2];
+        let mut rows = vec![vec![0; 2]; 3];
+
+        set_slice! {
+            grid row(0) = copy &[1, 2, 3];
+            grid row(1) = fill 4;
+            grid col(2) = copy &[7, 8];
+            rows col(0) = copy &[1, 2, 3];
+        }
+
+        assert_eq!(grid, [[1, 2, 7], [4, 4, 8]]);
+        assert_eq!(rows, [[1, 0], [2, 0], [3, 0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (3) is invalid, expected: 2 (the number of rows)")]
+    fn test_nested_col_invalid_length() {
+        let mut grid = [[0; 3]; 2];
+
+        set_slice! {
+            grid col(0) = copy &[1, 2, 3];
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut weights = [1.0, 3.0, 4.0];
+        let mut signal = [0.0f32, -2.0, 1.0, 4.0];
+        let mut silent = [0.0; 3];
+
+        set_slice! {
+            weights = normalize sum;
+            signal[1..] = normalize max;
+            silent = normalize sum;
+            silent[..0] = normalize max;
+        }
+
+        assert_eq!(weights, [0.125, 0.375, 0.5]);
+        assert_eq!(signal, [0.0, -0.5, 0.25, 1.0]);
+        assert_eq!(silent, [0.0; 3]);
+
+        set_slice! {
+            weights = normalize checked max;
+        }
+
+        assert_eq!(weights, [0.25, 0.75, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: the sum (0) is invalid, expected a finite non-zero total")]
+    fn test_normalize_checked_zero() {
+        let mut weights = [1.0, -1.0];
+
+        set_slice! {
+            weights = normalize max;
+            weights = normalize checked sum;
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        let mut scratch = [7; 5];
+        let mut names = [Some('a'), Some('b')];
+
+        set_slice! {
+            scratch[1..3] = default;
+            names = default;
+        }
+
+        assert_eq!(scratch, [7, 0, 0, 7, 7]);
+        assert_eq!(names, [None, None]);
+    }
+
+    #[test]
+    fn test_repeat_n() {
+        let unit = [1, 2];
+        let mut buf = [0; 7];
+
+        set_slice! {
+            buf[1..] = repeat_n(3) copy &unit;
+            buf[..0] = repeat_n(4) copy &[];
+        }
+
+        assert_eq!(buf, [0, 1, 2, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (5) is invalid, expected: 6 (3 copies)")]
+    fn test_repeat_n_invalid_length() {
+        let mut buf = [0; 5];
+
+        set_slice! {
+            buf = repeat_n(3) copy &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_repeat() {
+        use self::std::string::String;
+
+        let mut buf = [0u8; 7];
+        let mut names = [String::new(), String::new(), String::new(), String::new()];
+
+        set_slice! {
+            buf[1..] = repeat &[0xde, 0xad];
+            buf[..0] = repeat &[];
+            names = repeat clone &[String::from("a"), String::from("b")];
+        }
+
+        assert_eq!(buf, [0, 0xde, 0xad, 0xde, 0xad, 0xde, 0xad]);
+        assert_eq!(names, ["a", "b", "a", "b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (5) is invalid, expected a multiple of the pattern length (2)")]
+    fn test_repeat_invalid_length() {
+        let mut buf = [0; 5];
+
+        set_slice! {
+            buf = repeat &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_add_unique() {
+        let mut members = [0; 5];
+        let mut len = 1;
+
+        members[0] = 3;
+        len = set_slice!(members[..len] = add_unique &[1, 3, 2, 1]);
+
+        assert_eq!(len, 3);
+        assert_eq!(members, [3, 1, 2, 0, 0]);
+
+        len = set_slice!(members[..len] = add_unique &[2, 3]);
+
+        assert_eq!(len, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: the set is full (2 elements)")]
+    fn test_add_unique_full() {
+        let mut members = [1, 0];
+
+        let _ = set_slice!(members[..1] = add_unique &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_single_index() {
+        use self::std::string::String;
+
+        let mut v = [0; 4];
+        let mut names = [String::new(), String::new()];
+        let name = String::from("b");
+
+        set_slice! {
+            v[1] = 5;
+            v[3] = move 7 * 2;
+            v[..1] = 1;
+            names[1] = move name;
+        }
+
+        assert_eq!(v, [1, 5, 0, 14]);
+        assert_eq!(names, ["", "b"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_single_index_out_of_bounds() {
+        let v: &mut [i32] = &mut [0; 2];
+
+        set_slice! {
+            v[2] = 1;
+        }
+    }
+
+    #[test]
+    fn test_const_range_copy() {
+        let mut v = [0; 6];
+        let src = [1, 2];
+
+        set_slice! {
+            v[..2] = copy &src;
+            v[2..=3] = clone &[3, 4];
+            v[4..6] = copy &[5, 6];
+            v[5..] = copy &src[1..];
+        }
+
+        assert_eq!(v, [1, 2, 3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn test_span() {
+        let mut buf = [0; 6];
+        let (off, len) = (1, 3);
+
+        set_slice! {
+            buf at(off, len) = copy &[1, 2, 3];
+            buf at(off + len, 2) = fill 9;
+            buf at(6, 0) = copy &[];
+        }
+
+        assert_eq!(buf, [0, 1, 2, 3, 9, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: span at(4, 3) is out of bounds for length 6")]
+    fn test_span_out_of_bounds() {
+        let mut buf = [0; 6];
+
+        set_slice! {
+            buf at(0, 6) = fill 1;
+            buf at(4, 3) = fill 1;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is out of bounds for length 6")]
+    fn test_span_overflow() {
+        let mut buf = [0; 6];
+
+        set_slice! {
+            buf at(2, usize::MAX) = fill 1;
+        }
+    }
+
+    #[test]
+    fn test_clone_copies_copy_types() {
+        use self::std::string::String;
+
+        #[derive(Copy, PartialEq, Debug)]
+        struct Loud(u8);
+
+        #[allow(clippy::non_canonical_clone_impl)] // a canonical clone would hide which path was taken
+        impl Clone for Loud {
+            fn clone(&self) -> Self {
+                panic!("the Copy fast path was not taken")
+            }
+        }
+
+        let mut loud = [Loud(0); 3];
+        let mut names = [String::new(), String::new()];
+
+        set_slice! {
+            loud[1..] = clone &[Loud(1), Loud(2)];
+            names = clone &[String::from("a"), String::from("b")];
+        }
+
+        assert_eq!(loud, [Loud(0), Loud(1), Loud(2)]);
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_clone_generic_falls_back() {
+        fn clone_into<T: Clone>(mut slice: &mut [T], value: &[T]) {
+            set_slice! { slice = clone value; }
+        }
+
+        let mut nums = [0; 2];
+        clone_into(&mut nums, &[1, 2]);
+
+        assert_eq!(nums, [1, 2]);
+    }
+
+    #[test]
+    fn test_as_mut_targets() {
+        use self::std::boxed::Box;
+        use self::std::vec;
+
+        fn fill_tail<S: AsMut<[i32]>>(target: &mut S) {
+            set_slice! {
+                target[1..] = copy &[8, 9];
+                target at(0, 1) = 7;
+            }
+        }
+
+        let mut arr = [0; 3];
+        let mut vec = vec![0; 3];
+        let mut boxed: Box<[i32]> = Box::new([0; 3]);
+
+        fill_tail(&mut arr);
+        fill_tail(&mut vec);
+        fill_tail(&mut boxed);
+
+        set_slice! {
+            vec = fill 1;
+            boxed[..2] = clone &[2, 3];
+        }
+
+        assert_eq!(arr, [7, 8, 9]);
+        assert_eq!(vec, [1, 1, 1]);
+        assert_eq!(*boxed, [2, 3, 9]);
+    }
+
+    #[test]
+    fn test_fixed_capacity_targets() {
+        use core::ops::Deref;
+
+        // a stand in for `heapless::Vec`, which only exposes its length as a slice
+        struct FixedVec {
+            buf: [u8; 8],
+            len: usize,
+        }
+
+        impl AsMut<[u8]> for FixedVec {
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.buf[..self.len]
+            }
+        }
+
+        impl Deref for FixedVec {
+            type Target = [u8];
+
+            fn deref(&self) -> &[u8] {
+                &self.buf[..self.len]
+            }
+        }
+
+        let data = FixedVec { buf: *b"headtail", len: 4 };
+        let mut buf = FixedVec { buf: [0; 8], len: 6 };
+
+        set_slice! {
+            buf[..4] = copy &data;
+            buf[4..] = fill b'!';
+        }
+
+        assert_eq!(*buf, *b"head!!");
+        assert_eq!(buf.buf[6..], [0, 0]);
+    }
+
+    #[test]
+    fn test_remainder() {
+        let mut buf = [0; 8];
+
+        let rest = set_slice!(buf[..2] = copy &[1, 2] => rest);
+        let rest = set_slice!(rest[1..=2] = 3, 4 => rest);
+        let rest = set_slice!(rest[..] = fill 5 => rest);
+
+        assert!(rest.is_empty());
+        assert_eq!(buf, [1, 2, 0, 3, 4, 5, 5, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn test_remainder_invalid_length() {
+        let mut buf = [0; 4];
+        let buf: &mut [i32] = &mut buf;
+
+        set_slice!(buf[..3] = copy &[1, 2] => rest);
+    }
+
+    #[test]
+    fn test_lut() {
+        let palette = [10, 20, 30];
+        let mut out = [0; 5];
+
+        set_slice! {
+            out[..3] = lut(&palette) &[2u8, 0, 1];
+            out[3..] = lut(&palette, clamp) &[1u16, 9];
+        }
+
+        assert_eq!(out, [30, 10, 20, 20, 30]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: index 3 at 1 is out of bounds for a table of length 3")]
+    fn test_lut_out_of_bounds() {
+        let palette = [10, 20, 30];
+        let mut out = [0; 2];
+
+        set_slice! {
+            out = lut(&palette) &[0u8, 0];
+            out = lut(&palette) &[0u8, 3];
+        }
+    }
+
+    #[test]
+    fn test_expression_targets() {
+        use self::std::vec::Vec;
+
+        struct Grid {
+            rows: Vec<[i32; 3]>,
+            calls: usize,
+        }
+
+        impl Grid {
+            fn row_mut(&mut self, row: usize) -> &mut [i32; 3] {
+                self.calls += 1;
+                &mut self.rows[row]
+            }
+
+            fn reset(&mut self) {
+                set_slice! {
+                    self.rows[0][..] = fill 1;
+                    self.row_mut(1)[1..] = copy &[2, 3];
+                }
+            }
+        }
+
+        let mut grid = Grid { rows: self::std::vec![[0; 3]; 2], calls: 0 };
+        let mut pair = ([0; 2], [0; 2]);
+
+        grid.reset();
+        set_slice! {
+            pair.1 = 4, 5;
+            pair.0[1] = 6;
+        }
+
+        let rest = set_slice!(grid.row_mut(0)[..1] = copy &[7] => rest);
+
+        assert_eq!(rest, [1, 1]);
+        assert_eq!(grid.rows, [[7, 1, 1], [0, 2, 3]]);
+        assert_eq!(grid.calls, 2);
+        assert_eq!(pair, ([0, 6], [4, 5]));
+    }
+
+    #[test]
+    fn test_step() {
+        use self::std::string::String;
+
+        let mut audio = [0; 8];
+        let mut names = [String::new(), String::new(), String::new()];
+
+        set_slice! {
+            audio[..; step 3] = 1, 2, 3;
+            audio[1..6; step 2] = copy &[4, 5, 6];
+            audio[7..; step 4] = 7;
+            names[..=2; step 2] = clone &[String::from("a"), String::from("b")];
+        }
+
+        assert_eq!(audio, [1, 4, 0, 5, 0, 6, 3, 7]);
+        assert_eq!(names, ["a", "", "b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3 (the number of slots with step 2)")]
+    fn test_step_invalid_length() {
+        let mut audio = [0; 5];
+
+        set_slice! {
+            audio[..; step 2] = copy &[1, 2];
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: step must be non-zero")]
+    fn test_step_zero() {
+        let mut audio = [0; 5];
+        let step = 0;
+
+        set_slice! {
+            audio[..; step step] = 1;
+        }
+    }
+
+    #[test]
+    fn test_check_overlap() {
+        let v = [0u32; 6];
+
+        super::__check_overlap(1, &v[..3], &v[3..]);
+        super::__check_overlap(1, &v[3..], &v[..3]);
+        super::__check_overlap(1, &v[2..2], &v[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3: source and destination overlap")]
+    fn test_check_overlap_panics() {
+        let v = [0u32; 6];
+
+        super::__check_overlap(3, &v[0..4], &v[1..5]);
+    }
+
+    #[test]
+    fn test_copy_within() {
+        let mut buf = [1, 2, 3, 4, 5, 6];
+
+        set_slice! {
+            buf[1..] = copy_within 0..4;
+            buf[..2] = copy_within 4..=5;
+            buf = copy_within 5..;
+        }
+
+        assert_eq!(buf, [6, 6, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (3) is invalid, expected: 2")]
+    fn test_copy_within_invalid_length() {
+        let mut buf = [0; 6];
+
+        set_slice! {
+            buf[4..] = copy_within 0..2;
+            buf[2..4] = copy_within 0..3;
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: source range 4..7 is out of bounds for length 6")]
+    fn test_copy_within_out_of_bounds() {
+        let mut buf = [0; 6];
+
+        set_slice! {
+            buf = copy_within 4..7;
+        }
+    }
+
+    #[test]
+    fn test_init() {
+        use self::std::mem::MaybeUninit;
+        use self::std::string::String;
+
+        let mut nums = [MaybeUninit::<i32>::uninit(); 4];
+        let mut names = [MaybeUninit::<String>::uninit(), MaybeUninit::<String>::uninit()];
+
+        set_slice! {
+            nums[2..] = init copy &[3, 4];
+        }
+
+        let head = set_slice!(nums[..2] = init copy &[1, 2]);
+        head[0] = 0;
+
+        let names = set_slice!(names = init clone &[String::from("a"), String::from("b")]);
+        let nums = set_slice!(nums = init copy &[5, 6, 7, 8]);
+
+        assert_eq!(nums, [5, 6, 7, 8]);
+        assert_eq!(names, ["a", "b"]);
+
+        for name in names {
+            // the clones are owned by the buffer, so they are dropped by hand
+            unsafe { self::std::ptr::drop_in_place(name) };
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_init_invalid_length() {
+        let mut nums = [self::std::mem::MaybeUninit::<i32>::uninit(); 2];
+
+        set_slice! {
+            nums = init copy &[1];
+        }
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let mut bytes = [0u8; 8];
+        let len: u32 = 0x0102_0304;
+
+        set_slice! {
+            bytes[..4] = from_bytes len;
+            bytes[4..6] = from_le_bytes 0x0506_u16;
+            bytes[6..] = from_be_bytes -2_i16;
+        }
+
+        assert_eq!(bytes[..4], len.to_ne_bytes());
+        assert_eq!(bytes[4..], [6, 5, 0xff, 0xfe]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: slice length (3) is invalid, expected: 4 (the size of the value)")]
+    fn test_from_bytes_invalid_length() {
+        let mut bytes = [0u8; 3];
+
+        set_slice! {
+            bytes = from_bytes 1_u32;
+        }
+    }
+
+    #[test]
+    fn test_const_set_slice() {
+        const fn build(n: usize) -> [u8; 8] {
+            let mut buf: [u8; 8];
+            let tail = [7, 8];
+
+            const_set_slice! {
+                buf = move [1; 8];
+                buf[..=1] = 2, 3;
+                buf[n..(n + 2)] = copy &tail;
+                buf[6..] = copy &[9, 9];
+            }
+
+            buf
+        }
+
+        const fn fill(slice: &mut [u8]) {
+            const_set_slice! {
+                slice[1..] = copy &[4, 5];
+            }
+        }
+
+        const BUILT: [u8; 8] = build(3);
+        const FILLED: [u8; 3] = {
+            let mut buf = [0; 3];
+            fill(&mut buf);
+            buf
+        };
+
+        assert_eq!(BUILT, [2, 3, 1, 7, 8, 1, 9, 9]);
+        assert_eq!(FILLED, [0, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "const_set_slice: the value length does not match the length of the range")]
+    fn test_const_set_slice_invalid_length() {
+        let mut buf = [0; 4];
+        let end = 3;
+
+        const_set_slice! {
+            buf[..end] = 1, 2;
+        }
+    }
+
+    #[test]
+    fn test_bytes() {
+        use self::std::string::String;
+
+        let mut buf = [0u8; 8];
+        let name = String::from("é!");
+
+        set_slice! {
+            buf[..5] = bytes "HELLO";
+            buf[5..8] = bytes &name;
+            buf[8..] = bytes "";
+        }
+
+        assert_eq!(&buf[..], "HELLOé!".as_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 1")]
+    fn test_bytes_counts_bytes() {
+        let mut buf = [0u8; 1];
+
+        set_slice! {
+            buf = bytes "é";
+        }
+    }
+
+    #[test]
+    fn test_saturating() {
+        use self::std::string::String;
+
+        let mut nums = [0; 5];
+        let mut names = [String::new(), String::new()];
+
+        set_slice! {
+            nums[1..] = saturating copy &[1, 2];
+            nums[..1] = saturating copy &[9, 9, 9];
+            names = saturating clone &[String::from("a")];
+        }
+
+        assert_eq!(nums, [9, 1, 2, 0, 0]);
+        assert_eq!(names, ["a", ""]);
+
+        let written = set_slice!(nums = saturating copy &[7; 8]);
+        assert_eq!(written, 5);
+        assert_eq!(nums, [7; 5]);
+
+        let written = set_slice!(nums[..] = saturating copy &[]);
+        assert_eq!(written, 0);
+        assert_eq!(nums, [7; 5]);
+    }
+
+    #[test]
+    fn test_reverse_copy() {
+        use self::std::string::String;
+
+        let mut bytes = [0u8; 6];
+        let mut names = [String::new(), String::new()];
+        let source = [1, 2, 3, 4];
+
+        set_slice! {
+            bytes[1..5] = reverse copy &source;
+            bytes[5..] = reverse copy &[9];
+            names = reverse clone &[String::from("a"), String::from("b")];
+        }
+
+        assert_eq!(bytes, [0, 4, 3, 2, 1, 9]);
+        assert_eq!(source, [1, 2, 3, 4]);
+        assert_eq!(names, ["b", "a"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (2) is invalid, expected: 3")]
+    fn test_reverse_copy_invalid_length() {
+        let mut bytes = [0u8; 3];
+
+        set_slice! {
+            bytes = reverse copy &[1, 2, 3];
+            bytes = reverse copy &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_multiple_targets() {
+        let mut buf = [0; 12];
+        let mut other = [0; 4];
+        let values = [1, 2, 3, 4];
+
+        set_slice! {
+            buf[0..4], buf[8..12], other[..] = copy &values;
+            buf[4..6], other[1..3] = clone &[7, 7];
+        }
+
+        assert_eq!(buf, [1, 2, 3, 4, 7, 7, 0, 0, 1, 2, 3, 4]);
+        assert_eq!(other, [1, 7, 7, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn test_multiple_targets_invalid_length() {
+        let mut buf = [0; 8];
+
+        set_slice! {
+            buf[..2], buf[4..7] = copy &[1, 2];
+        }
+    }
+
+    #[test]
+    fn test_set_array() {
+        use self::std::string::String;
+
+        let header = [1, 2];
+        let nums = set_array! {
+            [i32; 6]:
+            [..2] = copy &header;
+            [2..4] = 3, 4;
+            [5] = 6
+        };
+        let names = set_array! {
+            [String; 2]:
+            [1] = "b".into();
+        };
+        let filled = set_array! {
+            [u8; 3]:
+            = fill 7;
+        };
+        let empty = set_array! { [u8; 0]: };
+
+        assert_eq!(nums, [1, 2, 3, 4, 0, 6]);
+        assert_eq!(names, ["", "b"]);
+        assert_eq!(filled, [7; 3]);
+        assert_eq!(empty, []);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: value length (1) is invalid, expected: 2")]
+    fn test_set_array_invalid_length() {
+        let tail = [3];
+        let _ = set_array! {
+            [u8; 4]:
+            [..2] = copy &[1, 2];
+            [2..] = copy &tail[..];
+        };
+    }
+
+    #[test]
+    fn test_fill_family() {
+        use self::std::string::String;
+
+        let mut nums = [1; 6];
+        let mut names = [String::new(), String::new(), String::new()];
+
+        set_slice! {
+            nums[..2] = clone_fill 2;
+            nums[2..4] = copy_fill 3;
+            nums[4..] = default;
+            names[1..] = clone_fill String::from("x");
+        }
+
+        assert_eq!(nums, [2, 2, 3, 3, 0, 0]);
+        assert_eq!(names, ["", "x", "x"]);
+
+        assert_eq!(set_slice!(nums[1..] = clone_fill 4), [4; 5]);
+        assert_eq!(set_slice!(nums[..3] = fill_copy 5), [5; 3]);
+        assert_eq!(set_slice!(nums[3..] = copy_fill 6), [6; 3]);
+        assert_eq!(set_slice!(nums[1..5] = default), [0; 4]);
+        assert_eq!(set_slice!(names = default).len(), 3);
+        assert_eq!(nums, [5, 0, 0, 0, 0, 6]);
+        assert_eq!(names, ["", "", ""]);
+    }
+
+    #[test]
+    fn test_const_range_list() {
+        use self::std::vec::Vec;
+
+        let mut arr = [0; 6];
+        let mut vec: Vec<i32> = Vec::from([0; 6]);
+
+        set_slice! {
+            arr[0..2] = 1, 2;
+            arr[2..=3] = 3, 4;
+            arr[4..] = 5, 6;
+            vec[..2] = 1, 2;
+            vec[..=2] = 1, 2, 3;
+            vec[3..] = 4, 5, 6;
+        }
+
+        assert_eq!(arr, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(vec, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1: value length (2) is invalid, expected: 3")]
+    fn test_const_range_list_open_runtime() {
+        use self::std::vec;
+
+        let mut v = vec![0; 5];
+
+        set_slice! {
+            v[2..] = 1, 2;
+        }
+    }
 
-## copy
-the `REFERENCE` `&[T]` values are copied into the slice \
-`T` must implement `Copy`
+    #[test]
+    fn test_grid_block() {
+        use self::std::string::String;
+        use self::std::vec;
 
-## clone
-the `REFERENCE` `&[T]` values are cloned into the slice \
-`T` must implement `Clone`
+        let mut grid = [[0; 4]; 4];
+        let mut names = vec![vec![String::new(); 3]; 2];
 
-## copy
-the `REFERENCE` `&[T]` values are copied into the slice \
-`T` must implement `Copy`
+        set_slice! {
+            grid[..2][2..] = copy &[1, 2, 3, 4];
+            grid[2..=3][..] = copy &[5; 8];
+            grid[1..1][..] = copy &[];
+            names[1..][..2] = clone &[String::from("a"), String::from("b")];
+        }
 
-## unsafe copy
-**VERY UNSAFE** \
-the `REFERENCE` `&[T]` values are copied into the slice \
-internally this uses ::core::mem::transmute_copy \
-so, use this with caution, as it may cause undefined behaviour \
-**VERY UNSAFE**
+        assert_eq!(grid, [[0, 0, 1, 2], [0, 0, 3, 4], [5; 4], [5; 4]]);
+        assert_eq!(names[1], ["a", "b", ""]);
+    }
 
-# Cargo features
-This crate allows for use in no-std environment.
-*/
+    #[test]
+    #[should_panic(expected = "line 1: value length (3) is invalid, expected: 4 (2 rows of 2)")]
+    fn test_grid_block_invalid_length() {
+        let mut grid = [[0; 4]; 4];
 
-#[doc(hidden)]
-pub use core::ptr::swap as __swap_ptr;
-#[doc(hidden)]
-pub use core::mem::transmute_copy as __transmute_copy_mem;
+        set_slice! {
+            grid[..2][2..] = copy &[1, 2, 3];
+        }
+    }
 
-#[macro_export(local_inner_macros)]
-#[doc(hidden)]
-macro_rules! count {
-    ( )        => {0usize};
-    ($one:tt) => {1usize};
-    ($($pairs:tt $_p:tt)*) => {
-        count!($($pairs)*) << 1usize
-    };
-    ($odd:tt $($rest:tt)*) => {
-        count!($($rest)*) | 1usize
-    };
-}
+    #[test]
+    #[should_panic(expected = "line 1: column span (1) of row 1 is invalid, expected: 2")]
+    fn test_grid_block_ragged_rows() {
+        use self::std::vec;
 
-#[macro_export(local_inner_macros)]
-#[doc(hidden)]
-macro_rules! __set_slice_internals {
-    (copy $slice:expr, $value:expr) => {
-        $slice.copy_from_slice($value);
-    };
-    (clone $slice:expr, $value:expr) => {
-        $slice.clone_from_slice($value);
-    };
-    ($option:ident $slice:expr, $value:expr) => {
-        compile_error!(stringify!(invalid option $option, valid options are copy, clone))
-    };
+        let mut grid = vec![vec![0; 4], vec![0; 3]];
 
-    ($($ln:tt),* => move $slice:expr, $value:expr) => {{
-        const LINE: usize = count!($($ln)*);
+        set_slice! {
+            grid[..][2..] = copy &[1, 2, 3];
+        }
+    }
 
-        #[inline(always)]
-        fn set<T>(slice: &mut [T], value: &mut [T]) {
-            let (sl, vl) = (slice.len(), value.len());
+    #[test]
+    fn test_set_slice_debug() {
+        let mut buf = [0; 8];
+        let src = [1, 2];
 
-            assert_eq!(sl, vl, "line {}: value length ({}) is invalid, excepted: {}", LINE, vl, sl);
-            slice.swap_with_slice(value);
+        set_slice_debug! {
+            buf = fill 9;
+            buf[..2] = copy &src;
+            buf[2..4] = 3, 4;
+            buf[4..5] = 5;
+            buf[5..] = move [6, 7, 8];
         }
 
-        let mut val = $value; // capture value
-        set(&mut $slice, &mut val);
-    }};
-    ($($ln:tt),* => $slice:expr, $option:ident $value:expr) => {{
-        const LINE: usize = count!($($ln)*);
-        let input: &_ = $value;
-        let slice = &mut $slice;
-        let (il, sl) = (input.len(), slice.len());
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8]);
 
-        assert_eq!(il, sl, "ln({}) input length invalid: {}, expected: {}", LINE, il, sl);
+        let region = set_slice_debug!(buf[6..] = reverse);
 
-        __set_slice_internals!($option slice, input);
-    }};
-    ($($ln:tt),* => ref $slice:expr, $size:expr, $value:expr) => {{
-        const LINE: usize = count!($($ln)*);
-        
-        #[inline(always)]
-        fn set<T>(slice: &mut [T], value: &[T]) {
-            let (sl, vl) = (slice.len(), value.len());
+        assert_eq!(region, [8, 7]);
+    }
 
-            assert_eq!(sl, $size, "line {}: slice length ({}) is invalid, excepted: {}", LINE, sl, $size);
-            assert_eq!(vl, $size, "line {}: value length ({}) is invalid, excepted: {}", LINE, vl, $size);
-            
-            unsafe {
-                let slice = &mut *(slice as *mut [T] as *mut [T; $size]);
-                let value = &*(value as *const [T] as *const [T; $size]);
+    #[test]
+    #[should_panic(expected = "line 2 (buf [1..] = copy &src): value length (2) is invalid, expected: 3")]
+    fn test_set_slice_debug_names_copy() {
+        let mut buf = [0; 4];
+        let src = [1, 2];
 
-                *slice = $crate::__transmute_copy_mem(value);
-            }
+        set_slice_debug! {
+            buf[..2] = copy &src;
+            buf[1..] = copy &src;
         }
+    }
 
-        let input: &_ = $value;
-        let slice = &mut $slice;
+    #[test]
+    #[should_panic(expected = "line 3 (buf = move vec): value length (1) is invalid, expected: 2")]
+    fn test_set_slice_debug_names_move() {
+        use self::std::vec;
 
-        set(slice, input);
-    }};
-}
+        let mut buf = [0; 2];
+        let vec = vec![1];
 
-/// a macro for setting parts of slices, see crate level docs for more info 
-#[macro_export]
-macro_rules! set_slice {
-    // no range branches
-    (@$($ln:tt),* => unsafe $slice:ident: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => ref $slice, $size, $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        set_slice_debug! {
+            buf = default;
+            buf[0..2] = 1, 2;
+            buf = move vec;
+        }
+    }
 
-    (@$($ln:tt),* => $slice:ident = move $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => move $slice, $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+    // a single local named like a mode that takes no value is the mode, the parentheses keep it a list
+    #[test]
+    fn test_mode_names_shadow_locals() {
+        let reverse = 7;
+        let default = 8;
+        let mut v = [1, 2];
 
-    (@$($ln:tt),* => $slice:ident = $option:ident $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => $slice, $option $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        set_slice! {
+            v[..1] = reverse;
+            v = reverse;
+        }
 
-    (@$($ln:tt),* => $slice:ident = $($value:expr),+; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => move $slice, [$($value),+]);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        assert_eq!(v, [2, 1]);
 
-    // with range branches
-    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*]: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => ref $slice[$($range)*], $size, $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        set_slice! {
+            v[..1] = (reverse);
+            v[1..] = move [default];
+        }
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = move $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => move $slice[$($range)*], $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        assert_eq!(v, [7, 8]);
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $option:ident $value:expr; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => $slice[$($range)*], $option $value);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        set_slice! {
+            v[1..] = default;
+        }
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = $($value:expr),+; $($rest:tt)*) => {
-        __set_slice_internals!($($ln),* => move $slice[$($range)*], [$($value),+]);
-        set_slice!(@$($ln,)* 0 => $($rest)*);
-    };
+        assert_eq!(v, [7, 0]);
 
-    // errors and terminals
-    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*]: ($size:expr) = $value:expr; $($rest:tt)*) => {
-        compile_error!("Moving values into the slice is safe");
-    };
-    (@$($ln:tt),* => unsafe $slice:ident: ($size:expr) = $value:expr; $($rest:tt)*) => {
-        compile_error!("Moving values into the slice is safe");
-    };
-    
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*]: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Copying arbitrary references in unsafe");
-    };
-    (@$($ln:tt),* => $slice:ident: ($size:expr) = ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Copying arbitrary references in unsafe");
-    };
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Copying arbitrary references in unsafe");
-    };
-    (@$($ln:tt),* => $slice:ident= ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Copying arbitrary references in unsafe");
-    };
-    
-    (@$($ln:tt),* => unsafe $slice:ident[$($range:tt)*] = ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Unkown size: size must be an expression surrouned by parentheses");
-    };
-    (@$($ln:tt),* => unsafe $slice:ident= ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Unkown size: size must be an expression surrouned by parentheses");
-    };
+        set_slice! {
+            v[1..] = (default);
+        }
 
-    (@$($ln:tt),* => $slice:ident: $($rest:tt)*) => {
-        compile_error!("Invalid size: size must be an expression surrouned by parentheses");
-    };
+        assert_eq!(v, [7, 8]);
+    }
 
-    (@$($ln:tt),* => $slice:ident = ref $value:expr; $($rest:tt)*) => {
-        compile_error!("Option is missing: value should be of the form: \"{copy, clone} ref value\"")
-    };
+    #[test]
+    fn test_try_set_slice_try_fill_with() {
+        #[derive(Debug, PartialEq)]
+        enum Error {
+            Empty,
+            Length(::SetSliceError),
+        }
 
-    (@$($ln:tt),* => $slice:ident = ; $($rest:tt)*) => {
-        compile_error!("There must be a non-zero number of arguments in a list");
-    };
+        impl From<::SetSliceError> for Error {
+            fn from(e: ::SetSliceError) -> Self {
+                Error::Length(e)
+            }
+        }
 
-    (@$($ln:tt),* => $slice:ident $($rest:tt)*) => {
-        compile_error!("Punctuation is missing!");
-    };
+        fn open(pool: &[u8], handles: &mut [u8; 4]) -> Result<(), Error> {
+            let mut iter = pool.iter();
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*]: $($rest:tt)*) => {
-        compile_error!("Invalid size: size must be an expression surrouned by parentheses!");
-    };
+            try_set_slice! {
+                handles[..1] = 9;
+                handles[1..] = try_fill_with || iter.next().cloned().ok_or(Error::Empty);
+            }?;
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] = ; $($rest:tt)*) => {
-        compile_error!("There must be a non-zero number of arguments in a list!");
-    };
+            Ok(())
+        }
 
-    (@$($ln:tt),* => $slice:ident[$($range:tt)*] $($rest:tt)*) => {
-        compile_error!("Punctuation is missing!");
-    };
+        let mut handles = [0; 4];
 
-    (@$($ln:tt),* => ) => {};
-    () => {};
-    
-    (@$($ln:tt),* => [$($range:tt)*] $($rest:tt)*) => {
-        compile_error!("Missing identifier, there is a range, but no slice");
-    };
-    (@$($ln:tt),* => $($rest:tt)+) => {
-        compile_error!("Missing rvalue, there seems to be a missing slice to assign to");
-    };
-    ($($rest:tt)+) => {
-        set_slice!(@0 => $($rest)+);
-    };
-}
+        assert_eq!(open(&[1, 2, 3], &mut handles), Ok(()));
+        assert_eq!(handles, [9, 1, 2, 3]);
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+        let mut handles = [0; 4];
+
+        assert_eq!(open(&[5], &mut handles), Err(Error::Empty));
+        assert_eq!(handles, [9, 5, 0, 0]);
+
+        let mut buf = [0; 3];
+        let result = try_set_slice! {
+            buf = try_fill_with || Ok::<_, Error>(1);
+            buf[..2] = copy &[2, 3, 4];
+        };
+
+        assert_eq!(buf, [1, 1, 1]);
+        assert_eq!(result, Err(Error::Length(::SetSliceError { line: 2, statement_index: 1, kind: ::SetSliceErrorKind::Length, expected: 2, actual: 3 })));
     }
 
     #[test]
-    fn test_move_values() {
-        let mut v = [0; 6];
-        let value = 0;
-        let array = [2, 3]; 
-        let vec = [4, 5, 6];
+    #[cfg(feature = "smallvec")]
+    fn test_smallvec() {
+        use smallvec::SmallVec;
+
+        let mut sv: SmallVec<[u8; 4]> = SmallVec::new();
 
         set_slice! {
-            v[0..1] = value;
-            v[1..3] = move array;
-            v[3..] = move vec;
+            sv = extend &[1, 2, 3];
         }
-        // println!("{:?}", vec); // COMPILE ERROR: use after move
 
-        assert_eq!(v, [0, 2, 3, 4, 5, 6]);
+        assert!(!sv.spilled());
+
+        set_slice! {
+            sv = extend &[4, 5];
+            sv[..2] = copy &[9, 8];
+        }
+
+        assert!(sv.spilled());
+        assert_eq!(*sv, [9, 8, 3, 4, 5]);
+
+        let result = try_set_slice! {
+            sv = extend &[6];
+            sv = copy &[0; 6];
+            sv = copy &[1];
+        };
+
+        assert_eq!(result.map_err(|e| e.statement_index), Err(2));
+        assert_eq!(*sv, [0; 6]);
     }
 
     #[test]
-    fn test_full_range() {
-        let mut v = [0; 10];
+    #[cfg(feature = "smallvec")]
+    #[should_panic(expected = "line 1: value length (1) is invalid, expected: 2")]
+    fn test_smallvec_invalid_length() {
+        use smallvec::SmallVec;
+
+        let mut sv: SmallVec<[u8; 4]> = SmallVec::from_slice(&[1, 2]);
 
         set_slice! {
-            v[..] = 0, 1, 2, 3, 4, 5, 6, 7, 8, 9;
+            sv = copy &[3][..];
         }
+    }
 
-        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    #[test]
+    #[cfg(feature = "heapless")]
+    fn test_heapless() {
+        use heapless::Vec;
 
-        let mut v = [0; 10];
+        let data: Vec<u8, 64> = Vec::from_slice(b"head").unwrap();
+        let mut buf: Vec<u8, 64> = Vec::new();
+        buf.resize_default(6).unwrap();
+
+        let n = data.len();
 
         set_slice! {
-            v = 0, 1, 2, 3, 4, 5, 6, 7, 8, 9;
+            buf[..n] = copy &data;
+            buf[n..] = fill b'!';
         }
 
-        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(*buf, *b"head!!");
+
+        let mut small: Vec<u8, 8> = Vec::new();
+
+        let result = try_set_slice! {
+            small = extend &data;
+            small = extend &buf;
+        };
+
+        assert_eq!(result, Err(::SetSliceError { line: 2, statement_index: 1, kind: ::SetSliceErrorKind::Alloc, expected: 10, actual: 8 }));
+        assert_eq!(*small, *b"head");
     }
 
     #[test]
-    fn set_slice_test() {
-        let mut v = [0; 8];
-        let values = [4, 5, 6];
-        let array = [0, 2];
-        let deref = [7, 8];
+    #[cfg(feature = "heapless")]
+    #[should_panic(expected = "line 1: value length (4) is invalid, expected: 2")]
+    fn test_heapless_invalid_length() {
+        use heapless::Vec;
+
+        let data: Vec<u8, 64> = Vec::from_slice(b"head").unwrap();
+        let mut buf: Vec<u8, 64> = Vec::from_slice(&[0; 6]).unwrap();
 
         set_slice! {
-            v[1..=2] = copy &[5, 3];
-            v[3..6] = copy &values;
-            v[..2] = copy &array;
-            v[6..] = copy &deref;
+            buf at(4, 2) = copy &data;
         }
-        let _ = values;
-
-        assert_eq!(v, [0, 2, 3, 4, 5, 6, 7, 8]);
     }
 
     #[test]
-    fn set_slice_test_unsafe() {
-        #[derive(PartialEq, Debug)]
-        struct A(i32);
-        let mut v = [A(0), A(0), A(0), A(0), A(0), A(0), A(0), A(0)];
-        let values = [A(4), A(5), A(6)];
-        let array = [A(0), A(2)];
-        let deref = [A(7), A(8)];
-
-        set_slice! {
-            unsafe v[1..=2]: (2) = ref &[A(5), A(3)];
-            unsafe v[3..6]: (3) = ref &values;
-            unsafe v[..2]: (2) = ref &array;
-            unsafe v[6..]: (2) = ref &deref;
+    fn test_try_set_slice_as_mut_targets() {
+        fn fill<S: AsMut<[i32]>>(target: &mut S, values: &[i32]) -> Result<(), ::SetSliceError> {
+            try_set_slice! {
+                target[..1] = 7;
+                target = move [1, 2, 3];
+                target[1..] = copy values;
+                target = clone values;
+            }
         }
-        let _ = values;
 
-        assert_eq!(v, [A(0), A(2), A(3), A(4), A(5), A(6), A(7), A(8)]);
+        let mut arr = [0; 3];
+        let mut vec = self::std::vec![0; 3];
+
+        assert_eq!(fill(&mut arr, &[8, 9]).map_err(|e| e.statement_index), Err(3));
+        assert_eq!(fill(&mut vec, &[8, 9]).map_err(|e| e.statement_index), Err(3));
+        assert_eq!(arr, [1, 8, 9]);
+        assert_eq!(vec, [1, 8, 9]);
     }
 }
\ No newline at end of file
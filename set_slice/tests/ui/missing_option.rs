@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate set_slice;
+
+fn main() {
+    let mut slice = [0; 3];
+    let vec = vec![1, 2, 3];
+
+    set_slice! {
+        slice = &vec;
+    }
+}
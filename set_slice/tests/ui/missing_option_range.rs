@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate set_slice;
+
+fn main() {
+    let mut slice = [0; 4];
+    let vec = vec![1, 2, 3];
+    let start = 1;
+
+    set_slice! {
+        slice[start..] = &vec[..];
+    }
+}